@@ -36,6 +36,6 @@ fn main() {
 
     fs::remove_file("src/pb/google.protobuf.rs").unwrap();
 
-    Command::new("cargo").args(&["fmt"]).output().unwrap();
+    Command::new("cargo").args(["fmt"]).output().unwrap();
     println!("cargo:rerun-if-changed=protos/reservation.proto");
 }
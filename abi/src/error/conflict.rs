@@ -7,7 +7,7 @@ use regex::Regex;
 
 #[derive(Debug, Clone)]
 pub enum ReservationConflictInfo {
-    Parsed(ReservationConflict),
+    Parsed(Box<ReservationConflict>),
     UnParsed(String),
 }
 
@@ -15,6 +15,8 @@ pub enum ReservationConflictInfo {
 pub struct ReservationConflict {
     pub new: ReservationWindow,
     pub old: ReservationWindow,
+    /// capacity of the resource which was exceeded
+    pub capacity: i32,
 }
 
 #[derive(Debug, Clone)]
@@ -22,6 +24,8 @@ pub struct ReservationWindow {
     pub rid: String,
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
+    /// id of the reservation holding the window, postgres doesn't report it
+    pub id: Option<String>,
 }
 
 impl FromStr for ReservationConflictInfo {
@@ -29,13 +33,28 @@ impl FromStr for ReservationConflictInfo {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(conflict) = s.parse::<ReservationConflict>() {
-            Ok(ReservationConflictInfo::Parsed(conflict))
+            Ok(ReservationConflictInfo::Parsed(Box::new(conflict)))
         } else {
             Ok(ReservationConflictInfo::UnParsed(s.to_string()))
         }
     }
 }
 
+impl ReservationConflictInfo {
+    /// apply capacity reported in the hint of the error, e.g. `capacity: 2`
+    pub fn with_capacity_hint(mut self, hint: Option<&str>) -> Self {
+        let capacity = hint
+            .and_then(|h| h.strip_prefix("capacity: "))
+            .and_then(|c| c.parse().ok());
+
+        if let (ReservationConflictInfo::Parsed(conflict), Some(capacity)) = (&mut self, capacity) {
+            conflict.capacity = capacity;
+        }
+
+        self
+    }
+}
+
 impl FromStr for ReservationConflict {
     type Err = ();
 
@@ -51,6 +70,7 @@ impl TryFrom<ParsedInfo> for ReservationConflict {
         Ok(Self {
             new: value.new.try_into()?,
             old: value.old.try_into()?,
+            capacity: 1,
         })
     }
 }
@@ -71,6 +91,7 @@ impl TryFrom<HashMap<String, String>> for ReservationWindow {
             rid: value.get("resource_id").ok_or(())?.to_string(),
             start,
             end,
+            id: None,
         })
     }
 }
@@ -146,6 +167,16 @@ mod test {
         }
     }
 
+    #[test]
+    fn conflict_capacity_should_be_parsed_from_hint() {
+        let info: ReservationConflictInfo = ERROR_MSG.parse().unwrap();
+        let info = info.with_capacity_hint(Some("capacity: 3"));
+        match info {
+            ReservationConflictInfo::Parsed(conflict) => assert_eq!(conflict.capacity, 3),
+            ReservationConflictInfo::UnParsed(_) => panic!("should be parsed"),
+        }
+    }
+
     #[test]
     fn parse_time() {
         let time = DateTime::parse_from_str("2022-12-26 22:00:00+00", "%Y-%m-%d %H:%M:%S%#z").unwrap().with_timezone(&Utc);
@@ -1,9 +1,13 @@
 mod conflict;
+mod status;
 
 use sqlx::postgres::PgDatabaseError;
 use thiserror::Error;
 
-pub use crate::error::conflict::ReservationConflictInfo;
+use crate::{PolicyRule, ReservationStatus};
+
+pub use crate::error::conflict::{ReservationConflict, ReservationConflictInfo, ReservationWindow};
+pub use crate::error::status::{ErrorInfo, RpcStatus};
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -13,6 +17,15 @@ pub enum Error {
     #[error("Conflict Reservation")]
     ConflictError(ReservationConflictInfo),
 
+    #[error("{} occurrences of the series conflict with existing reservations", .0.len())]
+    SeriesConflict(Vec<ReservationConflictInfo>),
+
+    #[error("{} reservations of the bundle conflict with existing reservations", .0.len())]
+    BundleConflict(Vec<ReservationConflictInfo>),
+
+    #[error("none of the {} candidate resources is free", .0.len())]
+    PoolConflict(Vec<ReservationConflictInfo>),
+
     #[error("invalid start or end time of the reservation")]
     InvalidTime,
 
@@ -25,9 +38,102 @@ pub enum Error {
     #[error("invalid reservation id: {0}")]
     InvalidReservationId(String),
 
+    #[error("invalid series id: {0}")]
+    InvalidSeriesId(String),
+
+    #[error("invalid bundle id: {0}")]
+    InvalidBundleId(String),
+
+    #[error("a bundle needs at least one reservation")]
+    EmptyBundle,
+
+    #[error("invalid waitlist entry id: {0}")]
+    InvalidWaitlistEntryId(String),
+
+    #[error("invalid hold duration: {0}s")]
+    InvalidHoldDuration(i64),
+
+    #[error("invalid duration: {0}s")]
+    InvalidDuration(i64),
+
+    #[error("invalid idempotency key: {0}")]
+    InvalidIdempotencyKey(String),
+
+    #[error("idempotency key reused with a different request: {0}")]
+    IdempotencyKeyReused(String),
+
+    #[error("a block needs a reason")]
+    MissingBlockReason,
+
+    #[error("invalid capacity: {0}")]
+    InvalidCapacity(i32),
+
+    #[error("unknown resource: {0}")]
+    UnknownResource(String),
+
+    #[error("resource {0} is inactive")]
+    InactiveResource(String),
+
+    #[error("resource {0} already exists")]
+    ResourceAlreadyExists(String),
+
+    #[error("invalid time zone: {0}")]
+    InvalidTimeZone(String),
+
+    #[error("invalid opening hours or holidays: {0}")]
+    InvalidCalendar(String),
+
+    #[error("outside of opening hours: {0}")]
+    OutsideOpeningHours(String),
+
+    #[error("invalid slot: {0}")]
+    InvalidSlot(String),
+
+    #[error("invalid recurrence rule: {0}")]
+    InvalidRecurrenceRule(String),
+
+    #[error("invalid page size: {0}")]
+    InvalidPageSize(i64),
+
+    #[error("invalid cursor: {0}")]
+    InvalidCursor(i64),
+
+    #[error("invalid change id: {0}")]
+    InvalidChangeId(i64),
+
+    #[error("invalid consumer name: {0}")]
+    InvalidConsumer(String),
+
+    #[error("invalid actor: {0}")]
+    InvalidActor(String),
+
+    #[error("invalid transition from {from} to {to}")]
+    InvalidTransition { from: ReservationStatus, to: ReservationStatus },
+
+    #[error("{0} reservations can't be rescheduled")]
+    NotReschedulable(ReservationStatus),
+
+    #[error("invalid version: {0}")]
+    InvalidVersion(i64),
+
+    #[error("reservation has changed, expected version {expected} but it's at {actual}")]
+    VersionMismatch { expected: i64, actual: i64 },
+
+    #[error("policy violation: {reason}")]
+    PolicyViolation { rule: PolicyRule, reason: String },
+
+    #[error("invalid policy: {0}")]
+    InvalidPolicy(String),
+
     #[error("No reservation found by the given condition")]
     NotFound,
 
+    #[error("failed to read configuration file")]
+    ConfigReadError,
+
+    #[error("failed to parse configuration file")]
+    ConfigParseError,
+
     #[error("unknown error")]
     Unknown,
 }
@@ -39,11 +145,27 @@ impl From<sqlx::Error> for Error {
                 let pg_error: &PgDatabaseError = err_dyn.downcast_ref();
                 match (pg_error.code(), pg_error.schema(), pg_error.table()) {
                     ("23P01", Some("rsvp"), Some("reservations")) => {
-                        Error::ConflictError(pg_error.detail()
+                        let info = pg_error.detail()
                             .unwrap()
                             .parse::<ReservationConflictInfo>()
-                            .unwrap())
+                            .unwrap();
+                        Error::ConflictError(info.with_capacity_hint(pg_error.hint()))
                     }
+                    ("23505", Some("rsvp"), Some("resources")) => {
+                        Error::ResourceAlreadyExists(parse_key_value(pg_error.detail().unwrap_or_default()))
+                    }
+                    // raised by rsvp.reservation_capacity_check, detail is the resource id
+                    ("RV001", _, _) => Error::UnknownResource(pg_error.detail().unwrap_or_default().to_string()),
+                    ("RV002", _, _) => Error::InactiveResource(pg_error.detail().unwrap_or_default().to_string()),
+                    // raised by rsvp.check_calendar, detail says when the resource is open
+                    ("RV003", _, _) => Error::OutsideOpeningHours(pg_error.detail().unwrap_or_default().to_string()),
+                    // raised by rsvp.align_timespan, detail says which slots the resource takes
+                    ("RV004", _, _) => Error::InvalidSlot(pg_error.detail().unwrap_or_default().to_string()),
+                    // raised by rsvp.reservation_policy_check, hint is the rule broken and detail why
+                    ("RV005", _, _) => Error::PolicyViolation {
+                        rule: parse_policy_rule(pg_error.hint().unwrap_or_default()),
+                        reason: pg_error.detail().unwrap_or_default().to_string(),
+                    },
 
                     _ => Error::SqlError(sqlx::Error::Database(err_dyn))
                 }
@@ -53,3 +175,17 @@ impl From<sqlx::Error> for Error {
         }
     }
 }
+
+// extract value from unique violation detail like `Key (id)=(value) already exists.`
+fn parse_key_value(detail: &str) -> String {
+    detail.split_once(")=(")
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(value, _)| value.to_string())
+        .unwrap_or_else(|| detail.to_string())
+}
+
+// the rule as named in rsvp.policies, e.g. max_duration
+fn parse_policy_rule(rule: &str) -> PolicyRule {
+    PolicyRule::from_str_name(&format!("POLICY_RULE_{}", rule.to_uppercase()))
+        .unwrap_or(PolicyRule::Unknown)
+}
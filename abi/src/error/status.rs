@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+
+use prost::Message;
+use prost_types::Any;
+use tonic::codegen::Bytes;
+use tonic::{Code, Status};
+
+use crate::error::conflict::{ReservationConflict, ReservationWindow};
+use crate::error::{Error, ReservationConflictInfo};
+use crate::utils::convert_to_timestamp;
+use crate::{BundleConflictDetails, ConflictWindow, PoolConflictDetails, ReservationConflictDetails, SeriesConflictDetails};
+
+/// domain of the ErrorInfo attached to every error of the reservation service
+const ERROR_DOMAIN: &str = "reservation";
+const ERROR_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.ErrorInfo";
+const CONFLICT_DETAILS_TYPE_URL: &str = "type.googleapis.com/reservation.ReservationConflictDetails";
+const SERIES_CONFLICT_DETAILS_TYPE_URL: &str = "type.googleapis.com/reservation.SeriesConflictDetails";
+const BUNDLE_CONFLICT_DETAILS_TYPE_URL: &str = "type.googleapis.com/reservation.BundleConflictDetails";
+const POOL_CONFLICT_DETAILS_TYPE_URL: &str = "type.googleapis.com/reservation.PoolConflictDetails";
+
+/// google.rpc.Status, sent as the `grpc-status-details-bin` trailer
+#[derive(Clone, PartialEq, Message)]
+pub struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    pub code: i32,
+    #[prost(string, tag = "2")]
+    pub message: String,
+    #[prost(message, repeated, tag = "3")]
+    pub details: Vec<Any>,
+}
+
+/// google.rpc.ErrorInfo, tells the client why the request failed
+#[derive(Clone, PartialEq, Message)]
+pub struct ErrorInfo {
+    #[prost(string, tag = "1")]
+    pub reason: String,
+    #[prost(string, tag = "2")]
+    pub domain: String,
+    #[prost(map = "string, string", tag = "3")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl From<Error> for Status {
+    fn from(err: Error) -> Self {
+        let (code, reason) = code_and_reason(&err);
+
+        let mut metadata = HashMap::new();
+        let mut details = vec![];
+        let message = match &err {
+            Error::ConflictError(ReservationConflictInfo::Parsed(conflict)) => {
+                metadata.insert("resource_id".to_string(), conflict.old.rid.clone());
+                metadata.insert("start".to_string(), conflict.old.start.to_rfc3339());
+                metadata.insert("end".to_string(), conflict.old.end.to_rfc3339());
+                metadata.insert("capacity".to_string(), conflict.capacity.to_string());
+                details.push(to_any(CONFLICT_DETAILS_TYPE_URL, &ReservationConflictDetails::from(conflict.as_ref())));
+
+                if conflict.capacity > 1 {
+                    format!("{} is fully booked (capacity {}) around {} to {}",
+                            conflict.old.rid, conflict.capacity, conflict.old.start.to_rfc3339(), conflict.old.end.to_rfc3339())
+                } else {
+                    format!("{} is taken from {} to {}",
+                            conflict.old.rid, conflict.old.start.to_rfc3339(), conflict.old.end.to_rfc3339())
+                }
+            }
+            Error::ConflictError(ReservationConflictInfo::UnParsed(detail)) => {
+                format!("conflict reservation: {}", detail)
+            }
+            Error::SeriesConflict(conflicts) => {
+                metadata.insert("conflicts".to_string(), conflicts.len().to_string());
+                details.push(to_any(SERIES_CONFLICT_DETAILS_TYPE_URL, &SeriesConflictDetails { conflicts: parsed_conflicts(conflicts) }));
+
+                err.to_string()
+            }
+            Error::BundleConflict(conflicts) => {
+                metadata.insert("conflicts".to_string(), conflicts.len().to_string());
+                details.push(to_any(BUNDLE_CONFLICT_DETAILS_TYPE_URL, &BundleConflictDetails { conflicts: parsed_conflicts(conflicts) }));
+
+                err.to_string()
+            }
+            Error::PoolConflict(conflicts) => {
+                metadata.insert("candidates".to_string(), conflicts.len().to_string());
+                details.push(to_any(POOL_CONFLICT_DETAILS_TYPE_URL, &PoolConflictDetails { conflicts: parsed_conflicts(conflicts) }));
+
+                err.to_string()
+            }
+            Error::InvalidTransition { from, to } => {
+                metadata.insert("from".to_string(), from.to_string());
+                metadata.insert("to".to_string(), to.to_string());
+                err.to_string()
+            }
+            Error::VersionMismatch { expected, actual } => {
+                metadata.insert("expected".to_string(), expected.to_string());
+                metadata.insert("actual".to_string(), actual.to_string());
+                err.to_string()
+            }
+            Error::PolicyViolation { rule, .. } => {
+                metadata.insert("rule".to_string(), rule.as_str_name().to_string());
+                err.to_string()
+            }
+            // don't leak database internals to clients
+            Error::SqlError(_) => "internal database error".to_string(),
+            _ => err.to_string(),
+        };
+
+        let info = ErrorInfo { reason: reason.to_string(), domain: ERROR_DOMAIN.to_string(), metadata };
+        details.insert(0, to_any(ERROR_INFO_TYPE_URL, &info));
+
+        let status = RpcStatus { code: code as i32, message: message.clone(), details };
+
+        Status::with_details(code, message, Bytes::from(status.encode_to_vec()))
+    }
+}
+
+fn code_and_reason(err: &Error) -> (Code, &'static str) {
+    match err {
+        Error::SqlError(_) => (Code::Internal, "DATABASE_ERROR"),
+        Error::ConflictError(_) => (Code::AlreadyExists, "RESERVATION_CONFLICT"),
+        Error::SeriesConflict(_) => (Code::AlreadyExists, "SERIES_CONFLICT"),
+        Error::BundleConflict(_) => (Code::AlreadyExists, "BUNDLE_CONFLICT"),
+        Error::PoolConflict(_) => (Code::AlreadyExists, "POOL_CONFLICT"),
+        Error::InvalidTime => (Code::InvalidArgument, "INVALID_TIME"),
+        Error::InvalidUserId(_) => (Code::InvalidArgument, "INVALID_USER_ID"),
+        Error::InvalidResourceId(_) => (Code::InvalidArgument, "INVALID_RESOURCE_ID"),
+        Error::InvalidReservationId(_) => (Code::InvalidArgument, "INVALID_RESERVATION_ID"),
+        Error::InvalidSeriesId(_) => (Code::InvalidArgument, "INVALID_SERIES_ID"),
+        Error::InvalidBundleId(_) => (Code::InvalidArgument, "INVALID_BUNDLE_ID"),
+        Error::EmptyBundle => (Code::InvalidArgument, "EMPTY_BUNDLE"),
+        Error::InvalidWaitlistEntryId(_) => (Code::InvalidArgument, "INVALID_WAITLIST_ENTRY_ID"),
+        Error::InvalidHoldDuration(_) => (Code::InvalidArgument, "INVALID_HOLD_DURATION"),
+        Error::InvalidDuration(_) => (Code::InvalidArgument, "INVALID_DURATION"),
+        Error::InvalidIdempotencyKey(_) => (Code::InvalidArgument, "INVALID_IDEMPOTENCY_KEY"),
+        Error::IdempotencyKeyReused(_) => (Code::InvalidArgument, "IDEMPOTENCY_KEY_REUSED"),
+        Error::MissingBlockReason => (Code::InvalidArgument, "MISSING_BLOCK_REASON"),
+        Error::InvalidCapacity(_) => (Code::InvalidArgument, "INVALID_CAPACITY"),
+        Error::UnknownResource(_) => (Code::NotFound, "UNKNOWN_RESOURCE"),
+        Error::InactiveResource(_) => (Code::FailedPrecondition, "INACTIVE_RESOURCE"),
+        Error::OutsideOpeningHours(_) => (Code::FailedPrecondition, "OUTSIDE_OPENING_HOURS"),
+        Error::InvalidSlot(_) => (Code::InvalidArgument, "INVALID_SLOT"),
+        Error::InvalidTimeZone(_) => (Code::InvalidArgument, "INVALID_TIME_ZONE"),
+        Error::InvalidCalendar(_) => (Code::InvalidArgument, "INVALID_CALENDAR"),
+        Error::ResourceAlreadyExists(_) => (Code::AlreadyExists, "RESOURCE_ALREADY_EXISTS"),
+        Error::InvalidRecurrenceRule(_) => (Code::InvalidArgument, "INVALID_RECURRENCE_RULE"),
+        Error::InvalidPageSize(_) => (Code::InvalidArgument, "INVALID_PAGE_SIZE"),
+        Error::InvalidCursor(_) => (Code::InvalidArgument, "INVALID_CURSOR"),
+        Error::InvalidChangeId(_) => (Code::InvalidArgument, "INVALID_CHANGE_ID"),
+        Error::InvalidConsumer(_) => (Code::InvalidArgument, "INVALID_CONSUMER"),
+        Error::InvalidActor(_) => (Code::InvalidArgument, "INVALID_ACTOR"),
+        Error::InvalidTransition { .. } => (Code::FailedPrecondition, "INVALID_TRANSITION"),
+        Error::NotReschedulable(_) => (Code::FailedPrecondition, "NOT_RESCHEDULABLE"),
+        Error::InvalidVersion(_) => (Code::InvalidArgument, "INVALID_VERSION"),
+        Error::VersionMismatch { .. } => (Code::Aborted, "VERSION_MISMATCH"),
+        Error::PolicyViolation { .. } => (Code::FailedPrecondition, "POLICY_VIOLATION"),
+        Error::InvalidPolicy(_) => (Code::InvalidArgument, "INVALID_POLICY"),
+        Error::NotFound => (Code::NotFound, "NOT_FOUND"),
+        Error::ConfigReadError | Error::ConfigParseError => (Code::Internal, "CONFIG_ERROR"),
+        Error::Unknown => (Code::Unknown, "UNKNOWN"),
+    }
+}
+
+/// details of the conflicts that could be parsed
+fn parsed_conflicts(conflicts: &[ReservationConflictInfo]) -> Vec<ReservationConflictDetails> {
+    conflicts.iter()
+        .filter_map(|info| match info {
+            ReservationConflictInfo::Parsed(conflict) => Some(conflict.as_ref().into()),
+            ReservationConflictInfo::UnParsed(_) => None,
+        })
+        .collect()
+}
+
+fn to_any(type_url: &str, msg: &impl Message) -> Any {
+    Any { type_url: type_url.to_string(), value: msg.encode_to_vec() }
+}
+
+fn find_detail<T: Message + Default>(status: &Status, type_url: &str) -> Option<T> {
+    let status = RpcStatus::decode(status.details()).ok()?;
+
+    status.details.iter()
+        .find(|any| any.type_url == type_url)
+        .and_then(|any| T::decode(any.value.as_slice()).ok())
+}
+
+impl ErrorInfo {
+    /// extract the ErrorInfo from a status returned by the reservation service
+    pub fn from_status(status: &Status) -> Option<Self> {
+        find_detail(status, ERROR_INFO_TYPE_URL)
+    }
+}
+
+impl ReservationConflictDetails {
+    /// extract the conflict details from a status returned by the reservation service
+    pub fn from_status(status: &Status) -> Option<Self> {
+        find_detail(status, CONFLICT_DETAILS_TYPE_URL)
+    }
+}
+
+impl SeriesConflictDetails {
+    /// extract the conflicting occurrences from a status returned by the reservation service
+    pub fn from_status(status: &Status) -> Option<Self> {
+        find_detail(status, SERIES_CONFLICT_DETAILS_TYPE_URL)
+    }
+}
+
+impl BundleConflictDetails {
+    /// extract the conflicting reservations of a bundle from a status returned by the reservation service
+    pub fn from_status(status: &Status) -> Option<Self> {
+        find_detail(status, BUNDLE_CONFLICT_DETAILS_TYPE_URL)
+    }
+}
+
+impl PoolConflictDetails {
+    /// extract the conflicts of the candidates from a status returned by the reservation service
+    pub fn from_status(status: &Status) -> Option<Self> {
+        find_detail(status, POOL_CONFLICT_DETAILS_TYPE_URL)
+    }
+}
+
+impl From<&ReservationConflict> for ReservationConflictDetails {
+    fn from(conflict: &ReservationConflict) -> Self {
+        Self {
+            new: Some((&conflict.new).into()),
+            old: Some((&conflict.old).into()),
+            capacity: conflict.capacity,
+        }
+    }
+}
+
+impl From<&ReservationWindow> for ConflictWindow {
+    fn from(window: &ReservationWindow) -> Self {
+        Self {
+            resource_id: window.rid.clone(),
+            start: Some(convert_to_timestamp(&window.start)),
+            end: Some(convert_to_timestamp(&window.end)),
+            reservation_id: window.id.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::PolicyRule;
+    use super::*;
+
+    const ERROR_MSG: &str = "Key (resource_id, timespan)=(ocean-view-room-714, [\"2022-12-26 22:00:00+00\",\"2022-12-28 19:00:00+00\")) conflicts with existing key (resource_id, timespan)=(ocean-view-room-714, [\"2022-12-25 22:00:00+00\",\"2022-12-28 19:00:00+00\")).";
+
+    #[test]
+    fn conflict_error_should_carry_conflict_details() {
+        let mut info: ReservationConflictInfo = ERROR_MSG.parse().unwrap();
+        if let ReservationConflictInfo::Parsed(conflict) = &mut info {
+            conflict.old.id = Some("existing-id".to_string());
+        }
+
+        let status = Status::from(Error::ConflictError(info));
+        assert_eq!(status.code(), Code::AlreadyExists);
+        assert_eq!(status.message(), "ocean-view-room-714 is taken from 2022-12-25T22:00:00+00:00 to 2022-12-28T19:00:00+00:00");
+
+        let info = ErrorInfo::from_status(&status).unwrap();
+        assert_eq!(info.reason, "RESERVATION_CONFLICT");
+        assert_eq!(info.domain, "reservation");
+        assert_eq!(info.metadata["resource_id"], "ocean-view-room-714");
+
+        let details = ReservationConflictDetails::from_status(&status).unwrap();
+        let old = details.old.unwrap();
+        assert_eq!(old.resource_id, "ocean-view-room-714");
+        assert_eq!(old.start.unwrap().seconds, 1672005600);
+        assert_eq!(old.end.unwrap().seconds, 1672254000);
+        assert_eq!(old.reservation_id, "existing-id");
+        assert_eq!(details.new.unwrap().reservation_id, "");
+        assert_eq!(details.capacity, 1);
+    }
+
+    #[test]
+    fn series_conflict_should_carry_every_conflict() {
+        let info: ReservationConflictInfo = ERROR_MSG.parse().unwrap();
+        let status = Status::from(Error::SeriesConflict(vec![info.clone(), info]));
+        assert_eq!(status.code(), Code::AlreadyExists);
+        assert_eq!(status.message(), "2 occurrences of the series conflict with existing reservations");
+        assert_eq!(ErrorInfo::from_status(&status).unwrap().reason, "SERIES_CONFLICT");
+
+        let details = SeriesConflictDetails::from_status(&status).unwrap();
+        assert_eq!(details.conflicts.len(), 2);
+        assert_eq!(details.conflicts[0].new.as_ref().unwrap().start.as_ref().unwrap().seconds, 1672092000);
+    }
+
+    #[test]
+    fn pool_conflict_should_carry_every_candidate() {
+        let info: ReservationConflictInfo = ERROR_MSG.parse().unwrap();
+        let status = Status::from(Error::PoolConflict(vec![info.clone(), info]));
+        assert_eq!(status.code(), Code::AlreadyExists);
+        assert_eq!(status.message(), "none of the 2 candidate resources is free");
+
+        let info = ErrorInfo::from_status(&status).unwrap();
+        assert_eq!(info.reason, "POOL_CONFLICT");
+        assert_eq!(info.metadata["candidates"], "2");
+        assert_eq!(PoolConflictDetails::from_status(&status).unwrap().conflicts.len(), 2);
+    }
+
+    #[test]
+    fn errors_should_map_to_status_code() {
+        let status = Status::from(Error::NotFound);
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(ErrorInfo::from_status(&status).unwrap().reason, "NOT_FOUND");
+        assert!(ReservationConflictDetails::from_status(&status).is_none());
+
+        let status = Status::from(Error::InvalidUserId("".to_string()));
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let status = Status::from(Error::SqlError(sqlx::Error::PoolTimedOut));
+        assert_eq!(status.code(), Code::Internal);
+        assert_eq!(status.message(), "internal database error");
+    }
+
+    #[test]
+    fn version_mismatch_should_be_aborted() {
+        let status = Status::from(Error::VersionMismatch { expected: 1, actual: 3 });
+        assert_eq!(status.code(), Code::Aborted);
+
+        let info = ErrorInfo::from_status(&status).unwrap();
+        assert_eq!(info.reason, "VERSION_MISMATCH");
+        assert_eq!(info.metadata["expected"], "1");
+        assert_eq!(info.metadata["actual"], "3");
+
+        // an omitted version isn't a stale one
+        let status = Status::from(Error::InvalidVersion(0));
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(ErrorInfo::from_status(&status).unwrap().reason, "INVALID_VERSION");
+    }
+
+    #[test]
+    fn policy_violation_should_carry_rule() {
+        let status = Status::from(Error::PolicyViolation {
+            rule: PolicyRule::MaxDuration,
+            reason: "reservations last 4h at most".to_string(),
+        });
+        assert_eq!(status.code(), Code::FailedPrecondition);
+        assert_eq!(status.message(), "policy violation: reservations last 4h at most");
+
+        let info = ErrorInfo::from_status(&status).unwrap();
+        assert_eq!(info.reason, "POLICY_VIOLATION");
+        assert_eq!(info.metadata["rule"], "POLICY_RULE_MAX_DURATION");
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use crate::{Reservation, ReservationStatus};
+use crate::error::Error;
+
+const PRODID: &str = "-//reservation//reservation service//EN";
+// lines longer than 75 octets have to be folded (RFC 5545 3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// render reservations as an iCalendar (RFC 5545) feed named `name`, one VEVENT per reservation,
+/// `now` is used as DTSTAMP of every event. The user is only named in SUMMARY if user_id is set
+pub fn to_icalendar(name: &str, rsvps: &[Reservation], now: DateTime<Utc>) -> Result<String, Error> {
+    let mut ics = String::new();
+    push_line(&mut ics, "BEGIN:VCALENDAR");
+    push_line(&mut ics, "VERSION:2.0");
+    push_line(&mut ics, &format!("PRODID:{}", PRODID));
+    push_line(&mut ics, "CALSCALE:GREGORIAN");
+    push_line(&mut ics, &format!("X-WR-CALNAME:{}", escape(name)));
+
+    for rsvp in rsvps {
+        let timespan = rsvp.get_timespan()?;
+        let status = ReservationStatus::from_i32(rsvp.status).unwrap_or(ReservationStatus::Unknown);
+
+        push_line(&mut ics, "BEGIN:VEVENT");
+        push_line(&mut ics, &format!("UID:{}", rsvp.id));
+        push_line(&mut ics, &format!("DTSTAMP:{}", format_time(&now)));
+        push_line(&mut ics, &format!("DTSTART:{}", format_time(&timespan.start)));
+        push_line(&mut ics, &format!("DTEND:{}", format_time(&timespan.end)));
+        let summary = match rsvp.user_id.as_str() {
+            "" => rsvp.resource_id.clone(),
+            uid => format!("{} by {}", rsvp.resource_id, uid),
+        };
+        push_line(&mut ics, &format!("SUMMARY:{}", escape(&summary)));
+        if !rsvp.note.is_empty() {
+            push_line(&mut ics, &format!("DESCRIPTION:{}", escape(&rsvp.note)));
+        }
+        push_line(&mut ics, &format!("LOCATION:{}", escape(&rsvp.resource_id)));
+        if let Some(status) = event_status(status) {
+            push_line(&mut ics, &format!("STATUS:{}", status));
+        }
+        // the version starts from 1, SEQUENCE from 0
+        push_line(&mut ics, &format!("SEQUENCE:{}", (rsvp.version - 1).max(0)));
+        push_line(&mut ics, "END:VEVENT");
+    }
+
+    push_line(&mut ics, "END:VCALENDAR");
+
+    Ok(ics)
+}
+
+/// STATUS of the VEVENT, None for a reservation in an unknown status
+fn event_status(status: ReservationStatus) -> Option<&'static str> {
+    match status {
+        ReservationStatus::Pending => Some("TENTATIVE"),
+        ReservationStatus::Confirmed | ReservationStatus::Blocked => Some("CONFIRMED"),
+        ReservationStatus::Cancelled | ReservationStatus::Rejected | ReservationStatus::Released => Some("CANCELLED"),
+        ReservationStatus::Unknown => None,
+    }
+}
+
+fn format_time(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// fold the content line without splitting a character, continuation lines start with a space
+fn push_line(ics: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            ics.push_str("\r\n ");
+            octets = 1;
+        }
+        ics.push(c);
+        octets += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn to_icalendar_should_render_one_event_per_reservation() {
+        let now: DateTime<Utc> = "2023-01-02T08:00:00Z".parse().unwrap();
+        let mut pending = Reservation::new_pending("gyg", "room-1",
+                                                   "2022-12-25T15:00:00-0700".parse().unwrap(),
+                                                   "2022-12-28T12:00:00-0700".parse().unwrap(),
+                                                   "late check-in; bring keys, please");
+        pending.id = "first".to_string();
+        pending.version = 1;
+        let mut cancelled = pending.clone();
+        cancelled.id = "second".to_string();
+        cancelled.status = ReservationStatus::Cancelled as i32;
+        cancelled.version = 3;
+        cancelled.note = "a".repeat(100);
+        cancelled.user_id.clear();
+
+        let ics = to_icalendar("room-1", &[pending, cancelled], now).unwrap();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("UID:first\r\nDTSTAMP:20230102T080000Z\r\nDTSTART:20221225T220000Z\r\nDTEND:20221228T190000Z\r\n"));
+        assert!(ics.contains("DESCRIPTION:late check-in\\; bring keys\\, please\r\n"));
+        assert!(ics.contains("SUMMARY:room-1 by gyg\r\n"));
+        // the user is left out of the second one
+        assert!(ics.contains("SUMMARY:room-1\r\n"));
+        assert!(ics.contains("STATUS:TENTATIVE\r\nSEQUENCE:0\r\n"));
+        assert!(ics.contains("STATUS:CANCELLED\r\nSEQUENCE:2\r\n"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert!(ics.contains(&format!("DESCRIPTION:{}\r\n {}\r\n", "a".repeat(63), "a".repeat(37))));
+    }
+}
@@ -1,11 +1,20 @@
+use sqlx::postgres::{PgHasArrayType, PgTypeInfo};
+use sqlx::postgres::types::PgInterval;
+
 pub use pb::*;
 
+mod config;
 mod error;
+mod icalendar;
 mod pb;
 mod types;
 mod utils;
 
-pub use error::{Error, ReservationConflictInfo};
+pub use config::{Config, DbConfig, ServerConfig};
+pub use types::{Frequency, RecurrenceRule};
+pub use icalendar::to_icalendar;
+pub use utils::convert_to_timestamp;
+pub use error::{Error, ErrorInfo, ReservationConflict, ReservationConflictInfo, ReservationWindow, RpcStatus};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
 #[sqlx(type_name = "reservation_status", rename_all = "lowercase")]
@@ -14,4 +23,41 @@ pub enum RsvpStatus {
     Pending,
     Confirmed,
     Blocked,
-}
\ No newline at end of file
+    Cancelled,
+    Rejected,
+    Released,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "reservation_update_type", rename_all = "lowercase")]
+pub enum RsvpUpdateType {
+    Unknown,
+    Create,
+    Update,
+    Delete,
+    Expire,
+    Promote,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "slot_rounding", rename_all = "lowercase")]
+pub enum RsvpSlotRounding {
+    Reject,
+    Outward,
+    Nearest,
+}
+
+/// rsvp.opening_hours, weekday is numbered as in ISO 8601 (monday is 1)
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "opening_hours")]
+pub struct RsvpOpeningHours {
+    pub weekday: i16,
+    pub opens_at: PgInterval,
+    pub closes_at: PgInterval,
+}
+
+impl PgHasArrayType for RsvpOpeningHours {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_opening_hours")
+    }
+}
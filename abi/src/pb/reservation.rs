@@ -1,5 +1,6 @@
 /// Core reservation object. Contains all the information for a reservation
-/// if ListenResponse op is DELETE, only id will be populated
+/// if ListenResponse op is DELETE or EXPIRE, only id will be populated
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Reservation {
     /// unique id for the reservation, if put into ReservationRequest, id should be empty
@@ -23,64 +24,366 @@ pub struct Reservation {
     /// extra note
     #[prost(string, tag = "7")]
     pub note: ::prost::alloc::string::String,
+    /// id of the series the reservation is an occurrence of, empty for a one-off reservation
+    #[prost(string, tag = "8")]
+    pub series_id: ::prost::alloc::string::String,
+    /// deadline to confirm a pending hold, empty if the reservation doesn't expire
+    #[prost(message, optional, tag = "9")]
+    pub expires_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// when the reservation was cancelled, empty unless status is CANCELLED
+    #[prost(message, optional, tag = "10")]
+    pub cancelled_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// user id of who cancelled the reservation
+    #[prost(string, tag = "11")]
+    pub cancelled_by: ::prost::alloc::string::String,
+    /// why the reservation was cancelled
+    #[prost(string, tag = "12")]
+    pub cancel_reason: ::prost::alloc::string::String,
+    /// bumped every time the reservation changes, starts from 1
+    #[prost(int64, tag = "13")]
+    pub version: i64,
+    /// id of the bundle the reservation was made in, empty if it was made on its own
+    #[prost(string, tag = "14")]
+    pub bundle_id: ::prost::alloc::string::String,
+}
+/// A series of reservations repeating by an RFC 5545 recurrence rule
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReservationSeries {
+    /// unique id for the series, if put into ReserveSeriesRequest, id should be empty
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// user id for the occurrences
+    #[prost(string, tag = "2")]
+    pub user_id: ::prost::alloc::string::String,
+    /// status of the occurrences when they are reserved
+    #[prost(enumeration = "ReservationStatus", tag = "3")]
+    pub status: i32,
+    /// resource id for the occurrences
+    #[prost(string, tag = "4")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// start time of the first occurrence (DTSTART), later occurrences start at the same local time of day
+    /// in time_zone
+    #[prost(message, optional, tag = "5")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end time of the first occurrence, all occurrences last as long as the first one
+    #[prost(message, optional, tag = "6")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// extra note for the occurrences
+    #[prost(string, tag = "7")]
+    pub note: ::prost::alloc::string::String,
+    /// RRULE value, e.g. FREQ=WEEKLY;BYDAY=TU;COUNT=52. FREQ, INTERVAL, COUNT, UNTIL and BYDAY (weekly only)
+    /// are supported, and one of COUNT and UNTIL is required
+    #[prost(string, tag = "8")]
+    pub rrule: ::prost::alloc::string::String,
+    /// start time of the occurrences excluded from the series (EXDATE)
+    #[prost(message, repeated, tag = "9")]
+    pub exdates: ::prost::alloc::vec::Vec<::prost_types::Timestamp>,
+    /// IANA time zone the occurrences are expanded in (TZID), e.g. Europe/Berlin, so they keep their local
+    /// time across DST changes. Empty means UTC
+    #[prost(string, tag = "10")]
+    pub time_zone: ::prost::alloc::string::String,
+}
+/// A resource which could be reserved
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Resource {
+    /// unique id of the resource, referenced by the resource_id of reservations
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// human readable name of the resource
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// type of the resource, e.g. meeting-room or desk
+    #[prost(string, tag = "3")]
+    pub resource_type: ::prost::alloc::string::String,
+    /// max number of reservations overlapping at any moment. When creating a resource, 0 means 1
+    #[prost(int32, tag = "4")]
+    pub capacity: i32,
+    /// inactive resources could not be reserved. New resources are always active
+    #[prost(bool, tag = "5")]
+    pub active: bool,
+    /// time kept free before each reservation of the resource, e.g. to prepare a room. Unset means none
+    #[prost(message, optional, tag = "6")]
+    pub buffer_before: ::core::option::Option<::prost_types::Duration>,
+    /// time kept free after each reservation of the resource, e.g. to clean a room. Unset means none
+    #[prost(message, optional, tag = "7")]
+    pub buffer_after: ::core::option::Option<::prost_types::Duration>,
+    /// IANA time zone of the opening hours and holidays, e.g. Europe/Berlin. Empty means UTC
+    #[prost(string, tag = "8")]
+    pub time_zone: ::prost::alloc::string::String,
+    /// a reservation has to fall within one of them. Empty means the resource is always open
+    #[prost(message, repeated, tag = "9")]
+    pub opening_hours: ::prost::alloc::vec::Vec<OpeningHours>,
+    /// dates (YYYY-MM-DD) the resource is closed the whole day, e.g. public holidays
+    #[prost(string, repeated, tag = "10")]
+    pub holidays: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// reservations start and end on multiples of slot, counted from midnight in the time zone of the resource.
+    /// Unset means any time
+    #[prost(message, optional, tag = "11")]
+    pub slot: ::core::option::Option<::prost_types::Duration>,
+    /// shortest a reservation could last, unset means no limit
+    #[prost(message, optional, tag = "12")]
+    pub min_duration: ::core::option::Option<::prost_types::Duration>,
+    /// longest a reservation could last, unset means no limit. Checked on the snapped timespan with INVALID_SLOT,
+    /// before Policy.max_duration, so the shorter of the two applies
+    #[prost(message, optional, tag = "13")]
+    pub max_duration: ::core::option::Option<::prost_types::Duration>,
+    /// what to do with reservations not starting or ending on a slot boundary
+    #[prost(enumeration = "SlotRounding", tag = "14")]
+    pub rounding: i32,
+}
+/// When a resource is open on a day of the week, in the time zone of the resource
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpeningHours {
+    #[prost(enumeration = "Weekday", tag = "1")]
+    pub weekday: i32,
+    /// offsets from midnight, closes_at could be 24h to stay open until the end of the day
+    #[prost(message, optional, tag = "2")]
+    pub opens_at: ::core::option::Option<::prost_types::Duration>,
+    #[prost(message, optional, tag = "3")]
+    pub closes_at: ::core::option::Option<::prost_types::Duration>,
+}
+/// To register a resource, send a CreateResourceRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// Created resource will be returned in CreateResourceResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To update name, type, capacity or active flag of a resource, send an UpdateResourceRequest.
+/// Fail with INVALID_CAPACITY if reservations still to come overlap more than the new capacity
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// Updated resource will be returned in UpdateResourceResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To get a resource, send a GetResourceRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// Resource will be returned in GetResourceResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// time window of a reservation involved in a conflict
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConflictWindow {
+    /// resource id of the window
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// start time of the window
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end time of the window
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// id of the reservation holding the window, empty if unknown
+    #[prost(string, tag = "4")]
+    pub reservation_id: ::prost::alloc::string::String,
+}
+/// sent in the error details (google.rpc.Status) when a reservation conflicts with an existing one
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReservationConflictDetails {
+    /// window requested by the rejected reservation
+    #[prost(message, optional, tag = "1")]
+    pub new: ::core::option::Option<ConflictWindow>,
+    /// window of the existing reservation
+    #[prost(message, optional, tag = "2")]
+    pub old: ::core::option::Option<ConflictWindow>,
+    /// capacity of the resource which was exceeded
+    #[prost(int32, tag = "3")]
+    pub capacity: i32,
+}
+/// sent in the error details (google.rpc.Status) when occurrences of a series conflict with existing reservations
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SeriesConflictDetails {
+    /// one entry for each conflicting occurrence, in the order of the occurrences
+    #[prost(message, repeated, tag = "1")]
+    pub conflicts: ::prost::alloc::vec::Vec<ReservationConflictDetails>,
+}
+/// sent in the error details (google.rpc.Status) when none of the candidates of a ReserveAnyRequest is free
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolConflictDetails {
+    /// one entry for each candidate resource, in the order they were tried
+    #[prost(message, repeated, tag = "1")]
+    pub conflicts: ::prost::alloc::vec::Vec<ReservationConflictDetails>,
+}
+/// sent in the error details (google.rpc.Status) when reservations of a bundle conflict with existing reservations
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BundleConflictDetails {
+    /// one entry for each conflicting reservation, in the order of the bundle
+    #[prost(message, repeated, tag = "1")]
+    pub conflicts: ::prost::alloc::vec::Vec<ReservationConflictDetails>,
 }
 /// To make a reservation, send a ReservationRequest with Reservation object (id should be empty)
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReserveRequest {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
+    /// if set, the reservation is a pending hold, released automatically unless confirmed within the duration
+    #[prost(message, optional, tag = "2")]
+    pub hold: ::core::option::Option<::prost_types::Duration>,
+    /// if set, a retried request with the same key (per user) returns the reservation made by the first one.
+    /// A retry asking for another resource, window or note fails with INVALID_ARGUMENT
+    #[prost(string, tag = "3")]
+    pub idempotency_key: ::prost::alloc::string::String,
 }
 /// Created reservation will be returned in ReserveResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReserveResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
-/// To update a reservation, send an UpdateRequest. Only note is updatable.
+/// To reserve whichever resource of a pool is free, send a ReserveAnyRequest.
+/// Candidates are tried in order and the first one free for the whole window is reserved
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveAnyRequest {
+    /// candidate resources, inactive ones are skipped
+    #[prost(string, repeated, tag = "1")]
+    pub resource_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// used if resource_ids is empty: all active resources of the type are candidates, ordered by id.
+    /// UNKNOWN_RESOURCE if the type has no active resource, INACTIVE_RESOURCE if no candidate is active
+    #[prost(string, tag = "2")]
+    pub resource_type: ::prost::alloc::string::String,
+    /// reservation to make (id should be empty), its resource id is set to the chosen resource
+    #[prost(message, optional, tag = "3")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// Reservation of the chosen resource will be returned in ReserveAnyResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveAnyResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// To update a reservation, send an UpdateRequest. Only note is updatable, use RescheduleRequest
+/// to change the time window or resource
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateRequest {
-    #[prost(int64, tag = "1")]
-    pub id: i64,
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
     #[prost(string, tag = "2")]
     pub note: ::prost::alloc::string::String,
+    /// version of the reservation the update is based on, fail with ABORTED if it's stale
+    #[prost(int64, tag = "3")]
+    pub version: i64,
 }
 /// Updated reservation will be returned in UpdateResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
 /// To change a reservation from pending to confirmed, send a ConfirmRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ConfirmRequest {
-    #[prost(int64, tag = "1")]
-    pub id: i64,
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// version of the reservation to confirm, fail with ABORTED if it's stale
+    #[prost(int64, tag = "2")]
+    pub version: i64,
 }
 /// Confirmed reservation will be returned in ConfirmResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ConfirmResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
-/// To cancel a reservation, send a CancelRequest
+/// To cancel a reservation, send a CancelRequest. Cancelled reservations are kept
+/// with status CANCELLED and no longer hold their time window
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CancelRequest {
-    #[prost(int64, tag = "1")]
-    pub id: i64,
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// user id of who cancels the reservation
+    #[prost(string, tag = "2")]
+    pub cancelled_by: ::prost::alloc::string::String,
+    /// why the reservation is cancelled
+    #[prost(string, tag = "3")]
+    pub reason: ::prost::alloc::string::String,
+    /// version of the reservation to cancel, fail with ABORTED if it's stale
+    #[prost(int64, tag = "4")]
+    pub version: i64,
 }
 /// Canceled reservation will be returned in CancelResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CancelResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// To move a reservation to another time window and/or resource, send a RescheduleRequest.
+/// The reservation keeps its current window unless the move succeeds
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RescheduleRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// resource to move to, empty to stay on the current one
+    #[prost(string, tag = "2")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// new start time, empty to keep the current one
+    #[prost(message, optional, tag = "3")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// new end time, empty to keep the current one
+    #[prost(message, optional, tag = "4")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// version of the reservation to move, fail with ABORTED if it's stale
+    #[prost(int64, tag = "5")]
+    pub version: i64,
+}
+/// Moved reservation will be returned in RescheduleResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RescheduleResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
 /// To get a reservation, send a GetRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetRequest {
-    #[prost(int64, tag = "1")]
-    pub id: i64,
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
 }
 /// Reservation will be returned in GetResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetResponse {
     #[prost(message, optional, tag = "1")]
@@ -89,6 +392,7 @@ pub struct GetResponse {
 /// query reservations with user id, resource id, start time, end time, and status
 #[derive(derive_builder::Builder)]
 #[builder(build_fn(name = "private_build"))]
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReservationQuery {
     /// resource id for the reservation query. If empty, query all resources
@@ -117,14 +421,16 @@ pub struct ReservationQuery {
     pub desc: bool,
 }
 /// To query reservations, send a QueryRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QueryRequest {
     #[prost(message, optional, tag = "1")]
     pub query: ::core::option::Option<ReservationQuery>,
 }
-/// query reservations, order by reservation id
+/// filter reservations page by page, order by the time reservations were made
 #[derive(derive_builder::Builder)]
 #[builder(build_fn(name = "private_build"))]
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReservationFilter {
     /// resource id for the reservation query. If empty, query all resources
@@ -139,10 +445,11 @@ pub struct ReservationFilter {
     #[prost(enumeration = "ReservationStatus", tag = "3")]
     #[builder(setter(into), default)]
     pub status: i32,
+    /// cursor returned by the prev/next of FilterPager. If empty, start from the first page
     #[prost(int64, optional, tag = "4")]
     #[builder(setter(into, strip_option), default)]
     pub cursor: ::core::option::Option<i64>,
-    /// page size for the query
+    /// page size for the query, between 1 and 100
     #[prost(int64, tag = "5")]
     #[builder(setter(into), default = "10")]
     pub page_size: i64,
@@ -152,21 +459,27 @@ pub struct ReservationFilter {
     pub desc: bool,
 }
 /// To query reservations, send a QueryRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FilterRequest {
     #[prost(message, optional, tag = "1")]
     pub filter: ::core::option::Option<ReservationFilter>,
 }
 /// filter pager info
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FilterPager {
+    /// cursor of the previous page, empty if current page is the first page
     #[prost(int64, optional, tag = "1")]
     pub prev: ::core::option::Option<i64>,
+    /// cursor of the next page, empty if current page is the last page
     #[prost(int64, optional, tag = "2")]
     pub next: ::core::option::Option<i64>,
+    /// total number of reservations matching the filter
     #[prost(int64, optional, tag = "3")]
     pub total: ::core::option::Option<i64>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FilterResponse {
     #[prost(message, repeated, tag = "1")]
@@ -174,10 +487,22 @@ pub struct FilterResponse {
     #[prost(message, optional, tag = "2")]
     pub pager: ::core::option::Option<FilterPager>,
 }
-/// Client can listen to reservation updates by sending a ListenRequest
+/// Client can listen to reservation updates by sending a ListenRequest.
+/// If neither last_change_id nor a known consumer is given, only changes made after listening are sent
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListenRequest {}
+pub struct ListenRequest {
+    /// resume after the change with this id, missed changes are replayed first
+    #[prost(int64, optional, tag = "1")]
+    pub last_change_id: ::core::option::Option<i64>,
+    /// named consumer, the server persists the id of the last change delivered to it
+    /// and resumes from there when the consumer listens again. A change is acknowledged once
+    /// the next one is sent, so the last change before a disconnect may be delivered again
+    #[prost(string, tag = "2")]
+    pub consumer: ::prost::alloc::string::String,
+}
 /// Server will send ListenResponse to client in streaming response
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListenResponse {
     /// update type
@@ -186,186 +511,821 @@ pub struct ListenResponse {
     /// id for updated reservation
     #[prost(message, optional, tag = "2")]
     pub reservation: ::core::option::Option<Reservation>,
+    /// id of the change, could be used as last_change_id to resume listening
+    #[prost(int64, tag = "3")]
+    pub change_id: i64,
 }
-/// reservation status for a given time period
-#[derive(
-    sqlx::Type, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration,
-)]
-#[repr(i32)]
-pub enum ReservationStatus {
-    Unknown = 0,
-    Pending = 1,
-    Confirmed = 2,
-    Blocked = 3,
+/// To block a resource for maintenance, send a BlockRequest. The block takes the whole resource:
+/// overlapping pending reservations are cancelled, while overlapping confirmed ones make it fail
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockRequest {
+    /// resource to block
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// user id of who blocks the resource
+    #[prost(string, tag = "2")]
+    pub user_id: ::prost::alloc::string::String,
+    /// start time of the block
+    #[prost(message, optional, tag = "3")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end time of the block
+    #[prost(message, optional, tag = "4")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// why the resource is blocked, saved as the note of the blocked reservation
+    #[prost(string, tag = "5")]
+    pub reason: ::prost::alloc::string::String,
 }
-impl ReservationStatus {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReservationStatus::Unknown => "RESERVATION_STATUS_UNKNOWN",
-            ReservationStatus::Pending => "RESERVATION_STATUS_PENDING",
-            ReservationStatus::Confirmed => "RESERVATION_STATUS_CONFIRMED",
-            ReservationStatus::Blocked => "RESERVATION_STATUS_BLOCKED",
-        }
-    }
+/// The blocked reservation and the pending reservations cancelled for it will be returned in BlockResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockResponse {
+    #[prost(message, optional, tag = "1")]
+    pub block: ::core::option::Option<Reservation>,
+    #[prost(message, repeated, tag = "2")]
+    pub released: ::prost::alloc::vec::Vec<Reservation>,
 }
-/// when reservation is updated, record the update type
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum ReservationUpdateType {
-    Unknown = 0,
-    Create = 1,
-    Update = 2,
-    Delete = 3,
+/// To move a reservation to another status, send a TransitionRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransitionRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// target status, must be reachable from the current status of the reservation
+    #[prost(enumeration = "ReservationStatus", tag = "2")]
+    pub status: i32,
+    /// version of the reservation to move, fail with ABORTED if it's stale
+    #[prost(int64, tag = "3")]
+    pub version: i64,
 }
-impl ReservationUpdateType {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReservationUpdateType::Unknown => "RESERVATION_UPDATE_TYPE_UNKNOWN",
-            ReservationUpdateType::Create => "RESERVATION_UPDATE_TYPE_CREATE",
-            ReservationUpdateType::Update => "RESERVATION_UPDATE_TYPE_UPDATE",
-            ReservationUpdateType::Delete => "RESERVATION_UPDATE_TYPE_DELETE",
-        }
-    }
+/// Updated reservation will be returned in TransitionResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransitionResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
 }
-/// Generated client implementations.
-pub mod reservation_service_client {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::http::Uri;
-    use tonic::codegen::*;
-    /// Reservation service
-    #[derive(Debug, Clone)]
-    pub struct ReservationServiceClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl ReservationServiceClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: std::convert::TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
-    }
-    impl<T> ReservationServiceClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
-        }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
-        }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> ReservationServiceClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
-                Into<StdError> + Send + Sync,
-        {
-            ReservationServiceClient::new(InterceptedService::new(inner, interceptor))
-        }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
-        }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
-        }
-        /// make a reservation
-        pub async fn reserve(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ReserveRequest>,
-        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/reserve");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        /// confirm a pending reservation, if reservation is not pending, do nothing
-        pub async fn confirm(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ConfirmRequest>,
-        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/confirm");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        /// update the reservation note
-        pub async fn update(
-            &mut self,
-            request: impl tonic::IntoRequest<super::UpdateRequest>,
-        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/update");
-            self.inner.unary(request.into_request(), path, codec).await
-        }
-        /// cancel a reservation
+/// To lift a block (blocked -> released), send an UnblockRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnblockRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// Lifted block will be returned in UnblockResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnblockResponse {
+    #[prost(message, optional, tag = "1")]
+    pub block: ::core::option::Option<Reservation>,
+}
+/// To reserve all occurrences of a series at once, send a ReserveSeriesRequest (id should be empty).
+/// Either all occurrences are reserved or none of them
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveSeriesRequest {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<ReservationSeries>,
+}
+/// Created series and its occurrences will be returned in ReserveSeriesResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveSeriesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<ReservationSeries>,
+    #[prost(message, repeated, tag = "2")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To change "this and following" occurrences of a series, send an UpdateSeriesRequest.
+/// Occurrences starting at or after `from` are replaced by the occurrences of the new series
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSeriesRequest {
+    /// id of the series to change
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// start time of the first occurrence to change
+    #[prost(message, optional, tag = "2")]
+    pub from: ::core::option::Option<::prost_types::Timestamp>,
+    /// definition of the series from then on (id should be empty), made by the same user as the series
+    #[prost(message, optional, tag = "3")]
+    pub series: ::core::option::Option<ReservationSeries>,
+    /// user id of who updates the series, the occurrences replaced are cancelled by them
+    #[prost(string, tag = "4")]
+    pub cancelled_by: ::prost::alloc::string::String,
+}
+/// The new series and its occurrences will be returned in UpdateSeriesResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSeriesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<ReservationSeries>,
+    #[prost(message, repeated, tag = "2")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To cancel "this and following" occurrences of a series, send a CancelSeriesRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSeriesRequest {
+    /// id of the series to cancel
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    /// start time of the first occurrence to cancel. If empty, cancel the whole series
+    #[prost(message, optional, tag = "2")]
+    pub from: ::core::option::Option<::prost_types::Timestamp>,
+    /// user id of who cancels the occurrences
+    #[prost(string, tag = "3")]
+    pub cancelled_by: ::prost::alloc::string::String,
+    /// why the occurrences are cancelled
+    #[prost(string, tag = "4")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// Cancelled occurrences will be returned in CancelSeriesResponse. Once all of its occurrences are
+/// cancelled, the series itself gets status CANCELLED
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSeriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To get a series, send a GetSeriesRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSeriesRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// Series will be returned in GetSeriesResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSeriesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<ReservationSeries>,
+}
+/// To find when resources are free, send an AvailabilityRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailabilityRequest {
+    /// resources to look at
+    #[prost(string, repeated, tag = "1")]
+    pub resource_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// start of the time window to search
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end of the time window to search
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// only return free intervals lasting at least this long, empty for any length
+    #[prost(message, optional, tag = "4")]
+    pub min_duration: ::core::option::Option<::prost_types::Duration>,
+    /// whether pending reservations take the window like confirmed ones. Blocks always do
+    #[prost(bool, tag = "5")]
+    pub include_pending: bool,
+}
+/// An interval a resource is free, i.e. it has capacity left and isn't blocked
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FreeSlot {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Free intervals ordered by start time will be returned in AvailabilityResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailabilityResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub slots: ::prost::alloc::vec::Vec<FreeSlot>,
+}
+/// To reserve several resources together, send a ReserveBundleRequest (ids should be empty).
+/// Either all of the reservations are made or none of them
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveBundleRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// Reservations of the bundle, sharing the bundle id, will be returned in ReserveBundleResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveBundleResponse {
+    #[prost(string, tag = "1")]
+    pub bundle_id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To confirm all pending reservations of a bundle at once, send a ConfirmBundleRequest.
+/// Fail with NOT_FOUND for an unknown bundle and FAILED_PRECONDITION if any of them can't be confirmed
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfirmBundleRequest {
+    #[prost(string, tag = "1")]
+    pub bundle_id: ::prost::alloc::string::String,
+}
+/// Confirmed reservations will be returned in ConfirmBundleResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfirmBundleResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To cancel all reservations of a bundle at once, send a CancelBundleRequest.
+/// Fail with NOT_FOUND for an unknown bundle and FAILED_PRECONDITION if none of them can be cancelled
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelBundleRequest {
+    #[prost(string, tag = "1")]
+    pub bundle_id: ::prost::alloc::string::String,
+    /// user id of who cancels the reservations
+    #[prost(string, tag = "2")]
+    pub cancelled_by: ::prost::alloc::string::String,
+    /// why the reservations are cancelled
+    #[prost(string, tag = "3")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// Cancelled reservations will be returned in CancelBundleResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelBundleResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// A user waiting for a window of a resource which was fully booked
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitlistEntry {
+    /// unique id of the entry, if put into JoinWaitlistRequest, id should be empty
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub user_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "5")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// note of the reservation made once the entry is promoted
+    #[prost(string, tag = "6")]
+    pub note: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "7")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// id of the pending reservation the entry was promoted to, empty while waiting
+    #[prost(string, tag = "8")]
+    pub reservation_id: ::prost::alloc::string::String,
+    /// the reservation the entry is promoted to is released unless confirmed within hold. Unset means an hour
+    #[prost(message, optional, tag = "9")]
+    pub hold: ::core::option::Option<::prost_types::Duration>,
+}
+/// To wait for a window, send a JoinWaitlistRequest. Once an overlapping reservation is cancelled or
+/// expires, the earliest entries fitting the freed window are promoted to pending holds, unless that breaks
+/// a rule of the resource. An entry for a window which is free already is promoted right away.
+/// Entries still waiting once their window has passed are dropped. Windows are snapped to the slots of the
+/// resource, those which couldn't be reserved for its slots or opening hours are rejected
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct JoinWaitlistRequest {
+    #[prost(message, optional, tag = "1")]
+    pub entry: ::core::option::Option<WaitlistEntry>,
+}
+/// The entry will be returned in JoinWaitlistResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct JoinWaitlistResponse {
+    #[prost(message, optional, tag = "1")]
+    pub entry: ::core::option::Option<WaitlistEntry>,
+}
+/// To stop waiting, send a LeaveWaitlistRequest. Promoted entries are reservations already, cancel them instead
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaveWaitlistRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// The removed entry will be returned in LeaveWaitlistResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaveWaitlistResponse {
+    #[prost(message, optional, tag = "1")]
+    pub entry: ::core::option::Option<WaitlistEntry>,
+}
+/// Limits on the reservations users could make, checked whenever a reservation is made or moved
+/// (including series, bundles and waitlist promotions). Blocks are not limited.
+/// A reservation has to satisfy both the policy of its resource and the policy for every resource.
+/// Unset or zero limits don't apply
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Policy {
+    /// resource the policy applies to, empty for the policy for every resource
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// max number of reservations of a user which haven't ended yet. The policy for every
+    /// resource counts the reservations of all resources, the others those of their resource
+    #[prost(int32, tag = "2")]
+    pub max_active_reservations: i32,
+    /// longest a reservation could last. Resource.max_duration is checked as well, the shorter one applies
+    #[prost(message, optional, tag = "3")]
+    pub max_duration: ::core::option::Option<::prost_types::Duration>,
+    /// how long before it starts a reservation has to be made at least
+    #[prost(message, optional, tag = "4")]
+    pub min_lead_time: ::core::option::Option<::prost_types::Duration>,
+    /// how far ahead a reservation could start at most
+    #[prost(message, optional, tag = "5")]
+    pub max_horizon: ::core::option::Option<::prost_types::Duration>,
+    /// reservations have to start at or after opens_at and end at or before closes_at of the same day, both counted
+    /// from midnight in the time zone of the resource. Reservations could span several days if neither is set
+    #[prost(message, optional, tag = "6")]
+    pub opens_at: ::core::option::Option<::prost_types::Duration>,
+    #[prost(message, optional, tag = "7")]
+    pub closes_at: ::core::option::Option<::prost_types::Duration>,
+}
+/// To set the policy of a resource (or for every resource), send a SetPolicyRequest. It replaces the previous one
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPolicyRequest {
+    #[prost(message, optional, tag = "1")]
+    pub policy: ::core::option::Option<Policy>,
+}
+/// The policy will be returned in SetPolicyResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPolicyResponse {
+    #[prost(message, optional, tag = "1")]
+    pub policy: ::core::option::Option<Policy>,
+}
+/// To get the policy of a resource (or for every resource if resource_id is empty), send a GetPolicyRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPolicyRequest {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+}
+/// The policy will be returned in GetPolicyResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPolicyResponse {
+    #[prost(message, optional, tag = "1")]
+    pub policy: ::core::option::Option<Policy>,
+}
+/// To get the timeline of a reservation, send a HistoryRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistoryRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// A change made to a reservation
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReservationHistoryEntry {
+    /// id of the entry, entries of a reservation are ordered by it
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// id of the changed reservation
+    #[prost(string, tag = "2")]
+    pub reservation_id: ::prost::alloc::string::String,
+    /// what kind of change it is
+    #[prost(enumeration = "ReservationUpdateType", tag = "3")]
+    pub op: i32,
+    /// who made the change, empty if unknown
+    #[prost(string, tag = "4")]
+    pub actor: ::prost::alloc::string::String,
+    /// reservation before the change, empty for CREATE
+    #[prost(message, optional, tag = "5")]
+    pub before: ::core::option::Option<Reservation>,
+    /// reservation after the change, empty for DELETE and EXPIRE
+    #[prost(message, optional, tag = "6")]
+    pub after: ::core::option::Option<Reservation>,
+    /// when the change was made
+    #[prost(message, optional, tag = "7")]
+    pub changed_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Changes made to the reservation, oldest first, will be returned in HistoryResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<ReservationHistoryEntry>,
+}
+/// reservation status for a given time period. A reservation moves between statuses by
+/// pending -> confirmed/rejected/cancelled, confirmed -> cancelled and blocked -> released.
+/// Cancelled, rejected and released are final, reservations in them don't hold their time window
+#[derive(sqlx::Type)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReservationStatus {
+    Unknown = 0,
+    Pending = 1,
+    Confirmed = 2,
+    Blocked = 3,
+    Cancelled = 4,
+    Rejected = 5,
+    Released = 6,
+}
+impl ReservationStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationStatus::Unknown => "RESERVATION_STATUS_UNKNOWN",
+            ReservationStatus::Pending => "RESERVATION_STATUS_PENDING",
+            ReservationStatus::Confirmed => "RESERVATION_STATUS_CONFIRMED",
+            ReservationStatus::Blocked => "RESERVATION_STATUS_BLOCKED",
+            ReservationStatus::Cancelled => "RESERVATION_STATUS_CANCELLED",
+            ReservationStatus::Rejected => "RESERVATION_STATUS_REJECTED",
+            ReservationStatus::Released => "RESERVATION_STATUS_RELEASED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RESERVATION_STATUS_UNKNOWN" => Some(Self::Unknown),
+            "RESERVATION_STATUS_PENDING" => Some(Self::Pending),
+            "RESERVATION_STATUS_CONFIRMED" => Some(Self::Confirmed),
+            "RESERVATION_STATUS_BLOCKED" => Some(Self::Blocked),
+            "RESERVATION_STATUS_CANCELLED" => Some(Self::Cancelled),
+            "RESERVATION_STATUS_REJECTED" => Some(Self::Rejected),
+            "RESERVATION_STATUS_RELEASED" => Some(Self::Released),
+            _ => None,
+        }
+    }
+}
+/// when reservation is updated, record the update type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReservationUpdateType {
+    Unknown = 0,
+    Create = 1,
+    Update = 2,
+    Delete = 3,
+    /// a pending hold was released because it wasn't confirmed in time
+    Expire = 4,
+    /// a waitlist entry became a pending reservation because its window was freed
+    Promote = 5,
+}
+impl ReservationUpdateType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationUpdateType::Unknown => "RESERVATION_UPDATE_TYPE_UNKNOWN",
+            ReservationUpdateType::Create => "RESERVATION_UPDATE_TYPE_CREATE",
+            ReservationUpdateType::Update => "RESERVATION_UPDATE_TYPE_UPDATE",
+            ReservationUpdateType::Delete => "RESERVATION_UPDATE_TYPE_DELETE",
+            ReservationUpdateType::Expire => "RESERVATION_UPDATE_TYPE_EXPIRE",
+            ReservationUpdateType::Promote => "RESERVATION_UPDATE_TYPE_PROMOTE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RESERVATION_UPDATE_TYPE_UNKNOWN" => Some(Self::Unknown),
+            "RESERVATION_UPDATE_TYPE_CREATE" => Some(Self::Create),
+            "RESERVATION_UPDATE_TYPE_UPDATE" => Some(Self::Update),
+            "RESERVATION_UPDATE_TYPE_DELETE" => Some(Self::Delete),
+            "RESERVATION_UPDATE_TYPE_EXPIRE" => Some(Self::Expire),
+            "RESERVATION_UPDATE_TYPE_PROMOTE" => Some(Self::Promote),
+            _ => None,
+        }
+    }
+}
+/// How a reservation not starting or ending on a slot boundary is handled
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SlotRounding {
+    /// fail with INVALID_SLOT
+    Reject = 0,
+    /// start at the previous and end at the next boundary, so the requested time is covered
+    Outward = 1,
+    /// start and end at the nearest boundary
+    Nearest = 2,
+}
+impl SlotRounding {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SlotRounding::Reject => "SLOT_ROUNDING_REJECT",
+            SlotRounding::Outward => "SLOT_ROUNDING_OUTWARD",
+            SlotRounding::Nearest => "SLOT_ROUNDING_NEAREST",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SLOT_ROUNDING_REJECT" => Some(Self::Reject),
+            "SLOT_ROUNDING_OUTWARD" => Some(Self::Outward),
+            "SLOT_ROUNDING_NEAREST" => Some(Self::Nearest),
+            _ => None,
+        }
+    }
+}
+/// Days of the week, numbered as in ISO 8601
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Weekday {
+    Unknown = 0,
+    Monday = 1,
+    Tuesday = 2,
+    Wednesday = 3,
+    Thursday = 4,
+    Friday = 5,
+    Saturday = 6,
+    Sunday = 7,
+}
+impl Weekday {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Weekday::Unknown => "WEEKDAY_UNKNOWN",
+            Weekday::Monday => "WEEKDAY_MONDAY",
+            Weekday::Tuesday => "WEEKDAY_TUESDAY",
+            Weekday::Wednesday => "WEEKDAY_WEDNESDAY",
+            Weekday::Thursday => "WEEKDAY_THURSDAY",
+            Weekday::Friday => "WEEKDAY_FRIDAY",
+            Weekday::Saturday => "WEEKDAY_SATURDAY",
+            Weekday::Sunday => "WEEKDAY_SUNDAY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "WEEKDAY_UNKNOWN" => Some(Self::Unknown),
+            "WEEKDAY_MONDAY" => Some(Self::Monday),
+            "WEEKDAY_TUESDAY" => Some(Self::Tuesday),
+            "WEEKDAY_WEDNESDAY" => Some(Self::Wednesday),
+            "WEEKDAY_THURSDAY" => Some(Self::Thursday),
+            "WEEKDAY_FRIDAY" => Some(Self::Friday),
+            "WEEKDAY_SATURDAY" => Some(Self::Saturday),
+            "WEEKDAY_SUNDAY" => Some(Self::Sunday),
+            _ => None,
+        }
+    }
+}
+/// A rule of a Policy, reported in the "rule" metadata when a reservation violates it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PolicyRule {
+    Unknown = 0,
+    /// the user has too many reservations which haven't ended yet
+    MaxActiveReservations = 1,
+    /// the reservation lasts too long
+    MaxDuration = 2,
+    /// the reservation starts too soon
+    MinLeadTime = 3,
+    /// the reservation starts too far ahead
+    MaxHorizon = 4,
+    /// the reservation is outside of the allowed hours
+    AllowedHours = 5,
+}
+impl PolicyRule {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PolicyRule::Unknown => "POLICY_RULE_UNKNOWN",
+            PolicyRule::MaxActiveReservations => "POLICY_RULE_MAX_ACTIVE_RESERVATIONS",
+            PolicyRule::MaxDuration => "POLICY_RULE_MAX_DURATION",
+            PolicyRule::MinLeadTime => "POLICY_RULE_MIN_LEAD_TIME",
+            PolicyRule::MaxHorizon => "POLICY_RULE_MAX_HORIZON",
+            PolicyRule::AllowedHours => "POLICY_RULE_ALLOWED_HOURS",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "POLICY_RULE_UNKNOWN" => Some(Self::Unknown),
+            "POLICY_RULE_MAX_ACTIVE_RESERVATIONS" => Some(Self::MaxActiveReservations),
+            "POLICY_RULE_MAX_DURATION" => Some(Self::MaxDuration),
+            "POLICY_RULE_MIN_LEAD_TIME" => Some(Self::MinLeadTime),
+            "POLICY_RULE_MAX_HORIZON" => Some(Self::MaxHorizon),
+            "POLICY_RULE_ALLOWED_HOURS" => Some(Self::AllowedHours),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod reservation_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// Reservation service
+    #[derive(Debug, Clone)]
+    pub struct ReservationServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl ReservationServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> ReservationServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ReservationServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            ReservationServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// make a reservation
+        pub async fn reserve(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReserveRequest>,
+        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reserve",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// reserve the first free resource of a pool
+        pub async fn reserve_any(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReserveAnyRequest>,
+        ) -> Result<tonic::Response<super::ReserveAnyResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reserve_any",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// confirm a pending reservation, fail with FAILED_PRECONDITION if reservation is not pending
+        pub async fn confirm(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ConfirmRequest>,
+        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/confirm",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// move a reservation to another status
+        pub async fn transition(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TransitionRequest>,
+        ) -> Result<tonic::Response<super::TransitionResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/transition",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// update the reservation note
+        pub async fn update(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateRequest>,
+        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/update",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// move a reservation to another time window and/or resource
+        pub async fn reschedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RescheduleRequest>,
+        ) -> Result<tonic::Response<super::RescheduleResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reschedule",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// cancel a reservation
         pub async fn cancel(
             &mut self,
             request: impl tonic::IntoRequest<super::CancelRequest>,
         ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/cancel");
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/cancel",
+            );
             self.inner.unary(request.into_request(), path, codec).await
         }
         /// get a reservation by id
@@ -373,69 +1333,446 @@ pub mod reservation_service_client {
             &mut self,
             request: impl tonic::IntoRequest<super::GetRequest>,
         ) -> Result<tonic::Response<super::GetResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/reservation.ReservationService/get");
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/get",
+            );
             self.inner.unary(request.into_request(), path, codec).await
         }
         /// query reservations by resource id, user id, status, start time, end time
         pub async fn query(
             &mut self,
             request: impl tonic::IntoRequest<super::QueryRequest>,
-        ) -> Result<tonic::Response<tonic::codec::Streaming<super::Reservation>>, tonic::Status>
-        {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/query");
+        ) -> Result<
+            tonic::Response<tonic::codec::Streaming<super::Reservation>>,
+            tonic::Status,
+        > {
             self.inner
-                .server_streaming(request.into_request(), path, codec)
+                .ready()
                 .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/query",
+            );
+            self.inner.server_streaming(request.into_request(), path, codec).await
         }
-        /// filter reservations, order by reservation id
+        /// filter reservations page by page, order by the time reservations were made
         pub async fn filter(
             &mut self,
             request: impl tonic::IntoRequest<super::FilterRequest>,
         ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/filter");
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/filter",
+            );
             self.inner.unary(request.into_request(), path, codec).await
         }
         /// another system could monitor newly added/confirmed/cancelled reservations
         pub async fn listen(
             &mut self,
             request: impl tonic::IntoRequest<super::ListenRequest>,
-        ) -> Result<tonic::Response<tonic::codec::Streaming<super::Reservation>>, tonic::Status>
-        {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
+        ) -> Result<
+            tonic::Response<tonic::codec::Streaming<super::ListenResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/listen");
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/listen",
+            );
+            self.inner.server_streaming(request.into_request(), path, codec).await
+        }
+        /// register a resource
+        pub async fn create_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateResourceRequest>,
+        ) -> Result<tonic::Response<super::CreateResourceResponse>, tonic::Status> {
             self.inner
-                .server_streaming(request.into_request(), path, codec)
+                .ready()
                 .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/create_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// update a resource
+        pub async fn update_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateResourceRequest>,
+        ) -> Result<tonic::Response<super::UpdateResourceResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/update_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get a resource by id
+        pub async fn get_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetResourceRequest>,
+        ) -> Result<tonic::Response<super::GetResourceResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/get_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// block a resource over a time range, e.g. for maintenance
+        pub async fn block(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BlockRequest>,
+        ) -> Result<tonic::Response<super::BlockResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/block",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// lift a block
+        pub async fn unblock(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UnblockRequest>,
+        ) -> Result<tonic::Response<super::UnblockResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/unblock",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// reserve all occurrences of a recurring series
+        pub async fn reserve_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReserveSeriesRequest>,
+        ) -> Result<tonic::Response<super::ReserveSeriesResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reserve_series",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// change "this and following" occurrences of a series
+        pub async fn update_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateSeriesRequest>,
+        ) -> Result<tonic::Response<super::UpdateSeriesResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/update_series",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// cancel "this and following" occurrences of a series
+        pub async fn cancel_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelSeriesRequest>,
+        ) -> Result<tonic::Response<super::CancelSeriesResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/cancel_series",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get a series by id
+        pub async fn get_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetSeriesRequest>,
+        ) -> Result<tonic::Response<super::GetSeriesResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/get_series",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// reserve several resources together, all or nothing
+        pub async fn reserve_bundle(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReserveBundleRequest>,
+        ) -> Result<tonic::Response<super::ReserveBundleResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reserve_bundle",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// confirm all reservations of a bundle
+        pub async fn confirm_bundle(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ConfirmBundleRequest>,
+        ) -> Result<tonic::Response<super::ConfirmBundleResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/confirm_bundle",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// cancel all reservations of a bundle
+        pub async fn cancel_bundle(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelBundleRequest>,
+        ) -> Result<tonic::Response<super::CancelBundleResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/cancel_bundle",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// find when resources are free within a time window
+        pub async fn availability(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AvailabilityRequest>,
+        ) -> Result<tonic::Response<super::AvailabilityResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/availability",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get every change made to a reservation
+        pub async fn history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HistoryRequest>,
+        ) -> Result<tonic::Response<super::HistoryResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/history",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// wait for a window of a fully booked resource
+        pub async fn join_waitlist(
+            &mut self,
+            request: impl tonic::IntoRequest<super::JoinWaitlistRequest>,
+        ) -> Result<tonic::Response<super::JoinWaitlistResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/join_waitlist",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// stop waiting for a window
+        pub async fn leave_waitlist(
+            &mut self,
+            request: impl tonic::IntoRequest<super::LeaveWaitlistRequest>,
+        ) -> Result<tonic::Response<super::LeaveWaitlistResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/leave_waitlist",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// set the limits on the reservations of a resource
+        pub async fn set_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPolicyRequest>,
+        ) -> Result<tonic::Response<super::SetPolicyResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/set_policy",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get the limits on the reservations of a resource
+        pub async fn get_policy(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPolicyRequest>,
+        ) -> Result<tonic::Response<super::GetPolicyResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/get_policy",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
     }
 }
@@ -443,7 +1780,7 @@ pub mod reservation_service_client {
 pub mod reservation_service_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
-    ///Generated trait containing gRPC methods that should be implemented for use with ReservationServiceServer.
+    /// Generated trait containing gRPC methods that should be implemented for use with ReservationServiceServer.
     #[async_trait]
     pub trait ReservationService: Send + Sync + 'static {
         /// make a reservation
@@ -451,16 +1788,31 @@ pub mod reservation_service_server {
             &self,
             request: tonic::Request<super::ReserveRequest>,
         ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status>;
-        /// confirm a pending reservation, if reservation is not pending, do nothing
+        /// reserve the first free resource of a pool
+        async fn reserve_any(
+            &self,
+            request: tonic::Request<super::ReserveAnyRequest>,
+        ) -> Result<tonic::Response<super::ReserveAnyResponse>, tonic::Status>;
+        /// confirm a pending reservation, fail with FAILED_PRECONDITION if reservation is not pending
         async fn confirm(
             &self,
             request: tonic::Request<super::ConfirmRequest>,
         ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status>;
+        /// move a reservation to another status
+        async fn transition(
+            &self,
+            request: tonic::Request<super::TransitionRequest>,
+        ) -> Result<tonic::Response<super::TransitionResponse>, tonic::Status>;
         /// update the reservation note
         async fn update(
             &self,
             request: tonic::Request<super::UpdateRequest>,
         ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status>;
+        /// move a reservation to another time window and/or resource
+        async fn reschedule(
+            &self,
+            request: tonic::Request<super::RescheduleRequest>,
+        ) -> Result<tonic::Response<super::RescheduleResponse>, tonic::Status>;
         /// cancel a reservation
         async fn cancel(
             &self,
@@ -471,8 +1823,10 @@ pub mod reservation_service_server {
             &self,
             request: tonic::Request<super::GetRequest>,
         ) -> Result<tonic::Response<super::GetResponse>, tonic::Status>;
-        ///Server streaming response type for the query method.
-        type queryStream: futures_core::Stream<Item = Result<super::Reservation, tonic::Status>>
+        /// Server streaming response type for the query method.
+        type queryStream: futures_core::Stream<
+                Item = Result<super::Reservation, tonic::Status>,
+            >
             + Send
             + 'static;
         /// query reservations by resource id, user id, status, start time, end time
@@ -480,20 +1834,112 @@ pub mod reservation_service_server {
             &self,
             request: tonic::Request<super::QueryRequest>,
         ) -> Result<tonic::Response<Self::queryStream>, tonic::Status>;
-        /// filter reservations, order by reservation id
+        /// filter reservations page by page, order by the time reservations were made
         async fn filter(
             &self,
             request: tonic::Request<super::FilterRequest>,
         ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status>;
-        ///Server streaming response type for the listen method.
-        type listenStream: futures_core::Stream<Item = Result<super::Reservation, tonic::Status>>
+        /// Server streaming response type for the listen method.
+        type listenStream: futures_core::Stream<
+                Item = Result<super::ListenResponse, tonic::Status>,
+            >
             + Send
             + 'static;
         /// another system could monitor newly added/confirmed/cancelled reservations
         async fn listen(
             &self,
-            request: tonic::Request<super::ListenRequest>,
-        ) -> Result<tonic::Response<Self::listenStream>, tonic::Status>;
+            request: tonic::Request<super::ListenRequest>,
+        ) -> Result<tonic::Response<Self::listenStream>, tonic::Status>;
+        /// register a resource
+        async fn create_resource(
+            &self,
+            request: tonic::Request<super::CreateResourceRequest>,
+        ) -> Result<tonic::Response<super::CreateResourceResponse>, tonic::Status>;
+        /// update a resource
+        async fn update_resource(
+            &self,
+            request: tonic::Request<super::UpdateResourceRequest>,
+        ) -> Result<tonic::Response<super::UpdateResourceResponse>, tonic::Status>;
+        /// get a resource by id
+        async fn get_resource(
+            &self,
+            request: tonic::Request<super::GetResourceRequest>,
+        ) -> Result<tonic::Response<super::GetResourceResponse>, tonic::Status>;
+        /// block a resource over a time range, e.g. for maintenance
+        async fn block(
+            &self,
+            request: tonic::Request<super::BlockRequest>,
+        ) -> Result<tonic::Response<super::BlockResponse>, tonic::Status>;
+        /// lift a block
+        async fn unblock(
+            &self,
+            request: tonic::Request<super::UnblockRequest>,
+        ) -> Result<tonic::Response<super::UnblockResponse>, tonic::Status>;
+        /// reserve all occurrences of a recurring series
+        async fn reserve_series(
+            &self,
+            request: tonic::Request<super::ReserveSeriesRequest>,
+        ) -> Result<tonic::Response<super::ReserveSeriesResponse>, tonic::Status>;
+        /// change "this and following" occurrences of a series
+        async fn update_series(
+            &self,
+            request: tonic::Request<super::UpdateSeriesRequest>,
+        ) -> Result<tonic::Response<super::UpdateSeriesResponse>, tonic::Status>;
+        /// cancel "this and following" occurrences of a series
+        async fn cancel_series(
+            &self,
+            request: tonic::Request<super::CancelSeriesRequest>,
+        ) -> Result<tonic::Response<super::CancelSeriesResponse>, tonic::Status>;
+        /// get a series by id
+        async fn get_series(
+            &self,
+            request: tonic::Request<super::GetSeriesRequest>,
+        ) -> Result<tonic::Response<super::GetSeriesResponse>, tonic::Status>;
+        /// reserve several resources together, all or nothing
+        async fn reserve_bundle(
+            &self,
+            request: tonic::Request<super::ReserveBundleRequest>,
+        ) -> Result<tonic::Response<super::ReserveBundleResponse>, tonic::Status>;
+        /// confirm all reservations of a bundle
+        async fn confirm_bundle(
+            &self,
+            request: tonic::Request<super::ConfirmBundleRequest>,
+        ) -> Result<tonic::Response<super::ConfirmBundleResponse>, tonic::Status>;
+        /// cancel all reservations of a bundle
+        async fn cancel_bundle(
+            &self,
+            request: tonic::Request<super::CancelBundleRequest>,
+        ) -> Result<tonic::Response<super::CancelBundleResponse>, tonic::Status>;
+        /// find when resources are free within a time window
+        async fn availability(
+            &self,
+            request: tonic::Request<super::AvailabilityRequest>,
+        ) -> Result<tonic::Response<super::AvailabilityResponse>, tonic::Status>;
+        /// get every change made to a reservation
+        async fn history(
+            &self,
+            request: tonic::Request<super::HistoryRequest>,
+        ) -> Result<tonic::Response<super::HistoryResponse>, tonic::Status>;
+        /// wait for a window of a fully booked resource
+        async fn join_waitlist(
+            &self,
+            request: tonic::Request<super::JoinWaitlistRequest>,
+        ) -> Result<tonic::Response<super::JoinWaitlistResponse>, tonic::Status>;
+        /// stop waiting for a window
+        async fn leave_waitlist(
+            &self,
+            request: tonic::Request<super::LeaveWaitlistRequest>,
+        ) -> Result<tonic::Response<super::LeaveWaitlistResponse>, tonic::Status>;
+        /// set the limits on the reservations of a resource
+        async fn set_policy(
+            &self,
+            request: tonic::Request<super::SetPolicyRequest>,
+        ) -> Result<tonic::Response<super::SetPolicyResponse>, tonic::Status>;
+        /// get the limits on the reservations of a resource
+        async fn get_policy(
+            &self,
+            request: tonic::Request<super::GetPolicyRequest>,
+        ) -> Result<tonic::Response<super::GetPolicyResponse>, tonic::Status>;
     }
     /// Reservation service
     #[derive(Debug)]
@@ -515,7 +1961,10 @@ pub mod reservation_service_server {
                 send_compression_encodings: Default::default(),
             }
         }
-        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
         where
             F: tonic::service::Interceptor,
         {
@@ -543,7 +1992,10 @@ pub mod reservation_service_server {
         type Response = http::Response<tonic::body::BoxBody>;
         type Error = std::convert::Infallible;
         type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
             Poll::Ready(Ok(()))
         }
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
@@ -551,16 +2003,443 @@ pub mod reservation_service_server {
             match req.uri().path() {
                 "/reservation.ReservationService/reserve" => {
                     #[allow(non_camel_case_types)]
-                    struct reserveSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::ReserveRequest> for reserveSvc<T> {
-                        type Response = super::ReserveResponse;
-                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                    struct reserveSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ReserveRequest>
+                    for reserveSvc<T> {
+                        type Response = super::ReserveResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reserve(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserveSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_any" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_anySvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ReserveAnyRequest>
+                    for reserve_anySvc<T> {
+                        type Response = super::ReserveAnyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveAnyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reserve_any(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_anySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/confirm" => {
+                    #[allow(non_camel_case_types)]
+                    struct confirmSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ConfirmRequest>
+                    for confirmSvc<T> {
+                        type Response = super::ConfirmResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConfirmRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).confirm(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = confirmSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/transition" => {
+                    #[allow(non_camel_case_types)]
+                    struct transitionSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::TransitionRequest>
+                    for transitionSvc<T> {
+                        type Response = super::TransitionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TransitionRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).transition(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = transitionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/update" => {
+                    #[allow(non_camel_case_types)]
+                    struct updateSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::UpdateRequest>
+                    for updateSvc<T> {
+                        type Response = super::UpdateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).update(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = updateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reschedule" => {
+                    #[allow(non_camel_case_types)]
+                    struct rescheduleSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::RescheduleRequest>
+                    for rescheduleSvc<T> {
+                        type Response = super::RescheduleResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RescheduleRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reschedule(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = rescheduleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancelSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::CancelRequest>
+                    for cancelSvc<T> {
+                        type Response = super::CancelResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).cancel(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancelSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/get" => {
+                    #[allow(non_camel_case_types)]
+                    struct getSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::GetRequest> for getSvc<T> {
+                        type Response = super::GetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = getSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/query" => {
+                    #[allow(non_camel_case_types)]
+                    struct querySvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::ServerStreamingService<super::QueryRequest>
+                    for querySvc<T> {
+                        type Response = super::Reservation;
+                        type ResponseStream = T::queryStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).query(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = querySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/filter" => {
+                    #[allow(non_camel_case_types)]
+                    struct filterSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::FilterRequest>
+                    for filterSvc<T> {
+                        type Response = super::FilterResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FilterRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).filter(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = filterSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/listen" => {
+                    #[allow(non_camel_case_types)]
+                    struct listenSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::ServerStreamingService<super::ListenRequest>
+                    for listenSvc<T> {
+                        type Response = super::ListenResponse;
+                        type ResponseStream = T::listenStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListenRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).listen(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = listenSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/create_resource" => {
+                    #[allow(non_camel_case_types)]
+                    struct create_resourceSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::CreateResourceRequest>
+                    for create_resourceSvc<T> {
+                        type Response = super::CreateResourceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ReserveRequest>,
+                            request: tonic::Request<super::CreateResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).reserve(request).await };
+                            let fut = async move {
+                                (*inner).create_resource(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -569,29 +2448,38 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = reserveSvc(inner);
+                        let method = create_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
-                            accept_compression_encodings,
-                            send_compression_encodings,
-                        );
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
                         let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/confirm" => {
+                "/reservation.ReservationService/update_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct confirmSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::ConfirmRequest> for confirmSvc<T> {
-                        type Response = super::ConfirmResponse;
-                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                    struct update_resourceSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::UpdateResourceRequest>
+                    for update_resourceSvc<T> {
+                        type Response = super::UpdateResourceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ConfirmRequest>,
+                            request: tonic::Request<super::UpdateResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).confirm(request).await };
+                            let fut = async move {
+                                (*inner).update_resource(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -600,29 +2488,38 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = confirmSvc(inner);
+                        let method = update_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
-                            accept_compression_encodings,
-                            send_compression_encodings,
-                        );
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
                         let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/update" => {
+                "/reservation.ReservationService/get_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct updateSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::UpdateRequest> for updateSvc<T> {
-                        type Response = super::UpdateResponse;
-                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                    struct get_resourceSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::GetResourceRequest>
+                    for get_resourceSvc<T> {
+                        type Response = super::GetResourceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdateRequest>,
+                            request: tonic::Request<super::GetResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).update(request).await };
+                            let fut = async move {
+                                (*inner).get_resource(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -631,29 +2528,35 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = updateSvc(inner);
+                        let method = get_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
-                            accept_compression_encodings,
-                            send_compression_encodings,
-                        );
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
                         let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/cancel" => {
+                "/reservation.ReservationService/block" => {
                     #[allow(non_camel_case_types)]
-                    struct cancelSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::CancelRequest> for cancelSvc<T> {
-                        type Response = super::CancelResponse;
-                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                    struct blockSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::BlockRequest> for blockSvc<T> {
+                        type Response = super::BlockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CancelRequest>,
+                            request: tonic::Request<super::BlockRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).cancel(request).await };
+                            let fut = async move { (*inner).block(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -662,29 +2565,36 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = cancelSvc(inner);
+                        let method = blockSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
-                            accept_compression_encodings,
-                            send_compression_encodings,
-                        );
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
                         let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/get" => {
+                "/reservation.ReservationService/unblock" => {
                     #[allow(non_camel_case_types)]
-                    struct getSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::GetRequest> for getSvc<T> {
-                        type Response = super::GetResponse;
-                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                    struct unblockSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::UnblockRequest>
+                    for unblockSvc<T> {
+                        type Response = super::UnblockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetRequest>,
+                            request: tonic::Request<super::UnblockRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get(request).await };
+                            let fut = async move { (*inner).unblock(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -693,33 +2603,38 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = getSvc(inner);
+                        let method = unblockSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
-                            accept_compression_encodings,
-                            send_compression_encodings,
-                        );
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
                         let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/query" => {
+                "/reservation.ReservationService/reserve_series" => {
                     #[allow(non_camel_case_types)]
-                    struct querySvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService>
-                        tonic::server::ServerStreamingService<super::QueryRequest> for querySvc<T>
-                    {
-                        type Response = super::Reservation;
-                        type ResponseStream = T::queryStream;
-                        type Future =
-                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                    struct reserve_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ReserveSeriesRequest>
+                    for reserve_seriesSvc<T> {
+                        type Response = super::ReserveSeriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::QueryRequest>,
+                            request: tonic::Request<super::ReserveSeriesRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).query(request).await };
+                            let fut = async move {
+                                (*inner).reserve_series(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -728,29 +2643,38 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = querySvc(inner);
+                        let method = reserve_seriesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
-                            accept_compression_encodings,
-                            send_compression_encodings,
-                        );
-                        let res = grpc.server_streaming(method, req).await;
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/filter" => {
+                "/reservation.ReservationService/update_series" => {
                     #[allow(non_camel_case_types)]
-                    struct filterSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::FilterRequest> for filterSvc<T> {
-                        type Response = super::FilterResponse;
-                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                    struct update_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::UpdateSeriesRequest>
+                    for update_seriesSvc<T> {
+                        type Response = super::UpdateSeriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FilterRequest>,
+                            request: tonic::Request<super::UpdateSeriesRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).filter(request).await };
+                            let fut = async move {
+                                (*inner).update_series(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -759,34 +2683,38 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = filterSvc(inner);
+                        let method = update_seriesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
-                            accept_compression_encodings,
-                            send_compression_encodings,
-                        );
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
                         let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/listen" => {
+                "/reservation.ReservationService/cancel_series" => {
                     #[allow(non_camel_case_types)]
-                    struct listenSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService>
-                        tonic::server::ServerStreamingService<super::ListenRequest>
-                        for listenSvc<T>
-                    {
-                        type Response = super::Reservation;
-                        type ResponseStream = T::listenStream;
-                        type Future =
-                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                    struct cancel_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::CancelSeriesRequest>
+                    for cancel_seriesSvc<T> {
+                        type Response = super::CancelSeriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListenRequest>,
+                            request: tonic::Request<super::CancelSeriesRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).listen(request).await };
+                            let fut = async move {
+                                (*inner).cancel_series(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -795,25 +2723,422 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = listenSvc(inner);
+                        let method = cancel_seriesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
-                            accept_compression_encodings,
-                            send_compression_encodings,
-                        );
-                        let res = grpc.server_streaming(method, req).await;
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/get_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct get_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::GetSeriesRequest>
+                    for get_seriesSvc<T> {
+                        type Response = super::GetSeriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_series(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = get_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_bundle" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_bundleSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ReserveBundleRequest>
+                    for reserve_bundleSvc<T> {
+                        type Response = super::ReserveBundleResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveBundleRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).reserve_bundle(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_bundleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/confirm_bundle" => {
+                    #[allow(non_camel_case_types)]
+                    struct confirm_bundleSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::ConfirmBundleRequest>
+                    for confirm_bundleSvc<T> {
+                        type Response = super::ConfirmBundleResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConfirmBundleRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).confirm_bundle(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = confirm_bundleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel_bundle" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancel_bundleSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::CancelBundleRequest>
+                    for cancel_bundleSvc<T> {
+                        type Response = super::CancelBundleResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelBundleRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).cancel_bundle(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancel_bundleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/availability" => {
+                    #[allow(non_camel_case_types)]
+                    struct availabilitySvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::AvailabilityRequest>
+                    for availabilitySvc<T> {
+                        type Response = super::AvailabilityResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AvailabilityRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).availability(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = availabilitySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/history" => {
+                    #[allow(non_camel_case_types)]
+                    struct historySvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::HistoryRequest>
+                    for historySvc<T> {
+                        type Response = super::HistoryResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HistoryRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).history(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = historySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/join_waitlist" => {
+                    #[allow(non_camel_case_types)]
+                    struct join_waitlistSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::JoinWaitlistRequest>
+                    for join_waitlistSvc<T> {
+                        type Response = super::JoinWaitlistResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::JoinWaitlistRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).join_waitlist(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = join_waitlistSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/leave_waitlist" => {
+                    #[allow(non_camel_case_types)]
+                    struct leave_waitlistSvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::LeaveWaitlistRequest>
+                    for leave_waitlistSvc<T> {
+                        type Response = super::LeaveWaitlistResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LeaveWaitlistRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).leave_waitlist(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = leave_waitlistSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/set_policy" => {
+                    #[allow(non_camel_case_types)]
+                    struct set_policySvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::SetPolicyRequest>
+                    for set_policySvc<T> {
+                        type Response = super::SetPolicyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPolicyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).set_policy(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = set_policySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/get_policy" => {
+                    #[allow(non_camel_case_types)]
+                    struct get_policySvc<T: ReservationService>(pub Arc<T>);
+                    impl<
+                        T: ReservationService,
+                    > tonic::server::UnaryService<super::GetPolicyRequest>
+                    for get_policySvc<T> {
+                        type Response = super::GetPolicyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPolicyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_policy(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = get_policySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                _ => Box::pin(async move {
-                    Ok(http::Response::builder()
-                        .status(200)
-                        .header("grpc-status", "12")
-                        .header("content-type", "application/grpc")
-                        .body(empty_body())
-                        .unwrap())
-                }),
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
             }
         }
     }
@@ -837,7 +3162,8 @@ pub mod reservation_service_server {
             write!(f, "{:?}", self.0)
         }
     }
-    impl<T: ReservationService> tonic::server::NamedService for ReservationServiceServer<T> {
+    impl<T: ReservationService> tonic::server::NamedService
+    for ReservationServiceServer<T> {
         const NAME: &'static str = "reservation.ReservationService";
     }
 }
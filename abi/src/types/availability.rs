@@ -0,0 +1,56 @@
+use std::ops::Range;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{FromRow, Row};
+use sqlx::postgres::PgRow;
+use sqlx::postgres::types::PgRange;
+
+use crate::{AvailabilityRequest, FreeSlot};
+use crate::error::Error;
+use crate::types::reservation::NaiveRange;
+use crate::utils::{convert_to_duration, convert_to_timestamp, convert_to_utc};
+
+impl AvailabilityRequest {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.resource_ids.is_empty() {
+            return Err(Error::InvalidResourceId("".to_string()));
+        }
+        if let Some(rid) = self.resource_ids.iter().find(|rid| rid.is_empty()) {
+            return Err(Error::InvalidResourceId(rid.clone()));
+        }
+
+        let timespan = self.get_timespan()?;
+        if timespan.start >= timespan.end {
+            return Err(Error::InvalidTime);
+        }
+
+        self.get_min_duration()?;
+
+        Ok(())
+    }
+
+    pub fn get_timespan(&self) -> Result<Range<DateTime<Utc>>, Error> {
+        let start = convert_to_utc(&self.start)?;
+        let end = convert_to_utc(&self.end)?;
+
+        Ok(Range { start, end })
+    }
+
+    /// zero if free intervals of any length are wanted
+    pub fn get_min_duration(&self) -> Result<Duration, Error> {
+        convert_to_duration(&self.min_duration)
+    }
+}
+
+impl FromRow<'_, PgRow> for FreeSlot {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let range: PgRange<DateTime<Utc>> = row.get("slot");
+        let range: NaiveRange<DateTime<Utc>> = range.into();
+
+        Ok(Self {
+            resource_id: row.get("resource_id"),
+            start: range.start.as_ref().map(convert_to_timestamp),
+            end: range.end.as_ref().map(convert_to_timestamp),
+        })
+    }
+}
@@ -0,0 +1,24 @@
+use crate::{BlockRequest, Reservation, ReservationStatus};
+use crate::error::Error;
+
+impl BlockRequest {
+    /// the blocked reservation requested, with the reason as its note
+    pub fn to_reservation(&self) -> Result<Reservation, Error> {
+        if self.reason.trim().is_empty() {
+            return Err(Error::MissingBlockReason);
+        }
+
+        let rsvp = Reservation {
+            user_id: self.user_id.clone(),
+            status: ReservationStatus::Blocked as i32,
+            resource_id: self.resource_id.clone(),
+            start: self.start.clone(),
+            end: self.end.clone(),
+            note: self.reason.clone(),
+            ..Default::default()
+        };
+        rsvp.validate()?;
+
+        Ok(rsvp)
+    }
+}
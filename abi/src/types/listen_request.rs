@@ -0,0 +1,23 @@
+use crate::ListenRequest;
+use crate::error::Error;
+
+impl ListenRequest {
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(id) = self.last_change_id {
+            if id < 0 {
+                return Err(Error::InvalidChangeId(id));
+            }
+        }
+
+        if self.consumer.len() > 64 {
+            return Err(Error::InvalidConsumer(self.consumer.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// named consumer whose offset should be persisted
+    pub fn get_consumer(&self) -> Option<&str> {
+        if self.consumer.is_empty() { None } else { Some(&self.consumer) }
+    }
+}
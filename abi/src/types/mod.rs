@@ -1,2 +1,16 @@
+mod availability;
+mod block_request;
+mod listen_request;
+mod policy;
+mod recurrence_rule;
 mod reservation;
-mod reservation_status;
\ No newline at end of file
+mod reservation_filter;
+mod reservation_history_entry;
+mod reservation_query;
+mod reservation_series;
+mod reservation_status;
+mod reservation_update_type;
+mod reschedule_request;
+mod resource;
+mod waitlist_entry;
+pub use recurrence_rule::{Frequency, RecurrenceRule};
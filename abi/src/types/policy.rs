@@ -0,0 +1,92 @@
+use chrono::Duration;
+use sqlx::{FromRow, Row};
+use sqlx::postgres::PgRow;
+use crate::Policy;
+use crate::error::Error;
+use crate::utils::{convert_interval, convert_to_duration};
+
+impl Policy {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.resource_id.len() > 64 {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+
+        if self.max_active_reservations < 0 {
+            return Err(Error::InvalidPolicy(format!(
+                "max active reservations can't be negative: {}", self.max_active_reservations)));
+        }
+
+        let (opens_at, closes_at) = self.get_allowed_hours()?;
+        if opens_at >= closes_at || closes_at > Duration::days(1) {
+            return Err(Error::InvalidPolicy(format!(
+                "allowed hours must be within a day: {}s to {}s", opens_at.num_seconds(), closes_at.num_seconds())));
+        }
+
+        self.get_limits()?;
+
+        Ok(())
+    }
+
+    /// max duration, min lead time and max horizon, zero if unset
+    pub fn get_limits(&self) -> Result<(Duration, Duration, Duration), Error> {
+        Ok((
+            convert_to_duration(&self.max_duration)?,
+            convert_to_duration(&self.min_lead_time)?,
+            convert_to_duration(&self.max_horizon)?,
+        ))
+    }
+
+    /// offsets from midnight reservations have to fall within, the whole day if unset
+    pub fn get_allowed_hours(&self) -> Result<(Duration, Duration), Error> {
+        let opens_at = convert_to_duration(&self.opens_at)?;
+        let closes_at = match convert_to_duration(&self.closes_at)? {
+            d if d.is_zero() => Duration::days(1),
+            d => d,
+        };
+
+        Ok((opens_at, closes_at))
+    }
+
+    /// whether reservations are limited to some hours of the day at all
+    pub fn has_allowed_hours(&self) -> bool {
+        self.opens_at.is_some() || self.closes_at.is_some()
+    }
+}
+
+impl FromRow<'_, PgRow> for Policy {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            resource_id: row.get("resource_id"),
+            max_active_reservations: row.get("max_active_reservations"),
+            max_duration: convert_interval(row.get("max_duration")),
+            min_lead_time: convert_interval(row.get("min_lead_time")),
+            max_horizon: convert_interval(row.get("max_horizon")),
+            opens_at: convert_interval(row.get("opens_at")),
+            closes_at: convert_interval(row.get("closes_at")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_should_reject_invalid_policy() {
+        let hours = |h: i64| Some(prost_types::Duration { seconds: h * 3600, nanos: 0 });
+        let policy = Policy { max_active_reservations: 2, opens_at: hours(8), closes_at: hours(20), ..Default::default() };
+        policy.validate().unwrap();
+        assert!(policy.has_allowed_hours());
+        assert!(!Policy { max_active_reservations: 2, ..Default::default() }.has_allowed_hours());
+
+        assert!(matches!(Policy { opens_at: hours(20), closes_at: hours(8), ..policy.clone() }.validate(), Err(Error::InvalidPolicy(_))));
+        assert!(matches!(Policy { closes_at: hours(25), ..policy.clone() }.validate(), Err(Error::InvalidPolicy(_))));
+        assert!(matches!(Policy { max_active_reservations: -1, ..policy.clone() }.validate(), Err(Error::InvalidPolicy(_))));
+
+        // too long to be an interval, rejected instead of overflowing
+        for seconds in [i64::MAX, 300 * 366 * 86_400] {
+            let policy = Policy { max_horizon: Some(prost_types::Duration { seconds, nanos: 0 }), ..policy.clone() };
+            assert!(matches!(policy.validate(), Err(Error::InvalidDuration(s)) if s == seconds));
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc, Weekday};
+
+use crate::error::Error;
+
+/// max number of occurrences a series could expand to
+const MAX_OCCURRENCES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// the subset of RFC 5545 RRULE supported for reservation series:
+/// FREQ, INTERVAL, COUNT, UNTIL and BYDAY without ordinals (weekly only).
+/// Occurrences keep the local time of day of dtstart in its time zone, UNTIL is in UTC
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+    pub by_day: Vec<Weekday>,
+}
+
+impl RecurrenceRule {
+    /// start time of the occurrences of a series starting at dtstart, EXDATE excluded
+    pub fn occurrences<Tz: TimeZone>(&self, dtstart: DateTime<Tz>, exdates: &[DateTime<Utc>]) -> Result<Vec<DateTime<Utc>>, Error> {
+        let mut ret = vec![];
+        let mut generated = 0;
+
+        for period in 0.. {
+            let candidates = self.candidates(&dtstart, period);
+            // later periods only start later, nothing left once a period starts after until
+            if let (Some(until), Some(first)) = (self.until, candidates.first()) {
+                if *first > until {
+                    break;
+                }
+            }
+
+            for start in candidates {
+                let start = start.with_timezone(&Utc);
+                if start < dtstart || self.until.is_some_and(|until| start > until) {
+                    continue;
+                }
+
+                generated += 1;
+                if generated > MAX_OCCURRENCES {
+                    return Err(Error::InvalidRecurrenceRule(format!("more than {} occurrences", MAX_OCCURRENCES)));
+                }
+                // COUNT counts the occurrences before EXDATE is applied
+                if !exdates.contains(&start) {
+                    ret.push(start);
+                }
+                if self.count.is_some_and(|count| generated >= count as usize) {
+                    return Ok(ret);
+                }
+            }
+
+            // skipped periods (e.g. Feb 30th) don't stop the series, but give up when nothing matches at all
+            if period as usize > MAX_OCCURRENCES * 12 {
+                break;
+            }
+        }
+
+        Ok(ret)
+    }
+
+    /// occurrences falling into the nth period after the period of dtstart, in order.
+    /// Periods are counted in local time, so occurrences don't shift across DST changes
+    fn candidates<Tz: TimeZone>(&self, dtstart: &DateTime<Tz>, period: u32) -> Vec<DateTime<Tz>> {
+        let local = dtstart.naive_local();
+        let step = period as i64 * self.interval as i64;
+        let candidates = match self.freq {
+            Frequency::Daily => vec![local + Duration::days(step)],
+            Frequency::Weekly => {
+                let week_start = local - Duration::days(local.weekday().num_days_from_monday() as i64)
+                    + Duration::weeks(step);
+                let mut days = if self.by_day.is_empty() { vec![local.weekday()] } else { self.by_day.clone() };
+                days.sort_by_key(|d| d.num_days_from_monday());
+                days.dedup();
+                days.into_iter()
+                    .map(|d| week_start + Duration::days(d.num_days_from_monday() as i64))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let months = local.month0() as i64 + step;
+                let year = local.year() as i64 + months / 12;
+                with_date(local, year, months as u32 % 12 + 1).into_iter().collect()
+            }
+            Frequency::Yearly => with_date(local, local.year() as i64 + step, local.month())
+                .into_iter()
+                .collect(),
+        };
+
+        candidates.iter()
+            .map(|local| from_local(&dtstart.timezone(), local))
+            .collect()
+    }
+}
+
+/// dt moved to the given year and month, none if the day doesn't exist in that month
+fn with_date(dt: NaiveDateTime, year: i64, month: u32) -> Option<NaiveDateTime> {
+    let date = NaiveDate::from_ymd_opt(i32::try_from(year).ok()?, month, dt.day())?;
+    Some(date.and_time(dt.time()))
+}
+
+/// the local time in tz. Like RFC 5545, an ambiguous time is the first of the two,
+/// and a time skipped by a DST change is taken with the offset before the change
+fn from_local<Tz: TimeZone>(tz: &Tz, local: &NaiveDateTime) -> DateTime<Tz> {
+    match tz.from_local_datetime(local) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => dt,
+        LocalResult::None => {
+            let before = tz.offset_from_utc_datetime(&(*local - Duration::days(1))).fix();
+            tz.from_utc_datetime(&(*local - Duration::seconds(before.local_minus_utc() as i64)))
+        }
+    }
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |msg: &str| Error::InvalidRecurrenceRule(format!("{}: {}", msg, s));
+        let rule = s.strip_prefix("RRULE:").unwrap_or(s);
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = vec![];
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once('=').ok_or_else(|| invalid("malformed rule part"))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => freq = Some(match value.to_ascii_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    _ => return Err(invalid("unsupported FREQ")),
+                }),
+                "INTERVAL" => interval = value.parse().ok().filter(|i| *i > 0).ok_or_else(|| invalid("invalid INTERVAL"))?,
+                "COUNT" => count = Some(value.parse().ok().filter(|c| *c > 0).ok_or_else(|| invalid("invalid COUNT"))?),
+                "UNTIL" => until = Some(parse_until(value).ok_or_else(|| invalid("invalid UNTIL"))?),
+                "BYDAY" => by_day = value.split(',')
+                    .map(parse_weekday)
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| invalid("invalid BYDAY"))?,
+                "WKST" if value.eq_ignore_ascii_case("MO") => {}
+                _ => return Err(invalid("unsupported rule part")),
+            }
+        }
+
+        let freq = freq.ok_or_else(|| invalid("missing FREQ"))?;
+        if count.is_some() == until.is_some() {
+            return Err(invalid("exactly one of COUNT and UNTIL is required"));
+        }
+        if !by_day.is_empty() && freq != Frequency::Weekly {
+            return Err(invalid("BYDAY is only supported for WEEKLY"));
+        }
+
+        Ok(Self { freq, interval, count, until, by_day })
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let freq = match self.freq {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={}", freq)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// UNTIL is a UTC date-time like 20231231T235959Z, or a date which includes the whole day
+fn parse_until(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.strip_suffix('Z').unwrap_or(value);
+    let dt = match NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        Ok(dt) => dt,
+        Err(_) => NaiveDate::parse_from_str(value, "%Y%m%d").ok()?
+            .and_time(NaiveTime::from_hms_opt(23, 59, 59)?),
+    };
+    Some(Utc.from_utc_datetime(&dt))
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value.to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn weekly_rule_should_expand() {
+        let rule: RecurrenceRule = "RRULE:FREQ=WEEKLY;BYDAY=TU;COUNT=3".parse().unwrap();
+        let ret = rule.occurrences(utc("2023-01-03T10:00:00Z"), &[]).unwrap();
+        assert_eq!(ret, vec![utc("2023-01-03T10:00:00Z"), utc("2023-01-10T10:00:00Z"), utc("2023-01-17T10:00:00Z")]);
+    }
+
+    #[test]
+    fn weekly_rule_with_days_before_dtstart_should_skip_them() {
+        // dtstart is a Wednesday, Monday of the first week is before it
+        let rule: RecurrenceRule = "FREQ=WEEKLY;BYDAY=MO,WE;INTERVAL=2;UNTIL=20230125T235959Z".parse().unwrap();
+        let ret = rule.occurrences(utc("2023-01-04T09:00:00Z"), &[]).unwrap();
+        assert_eq!(ret, vec![utc("2023-01-04T09:00:00Z"), utc("2023-01-16T09:00:00Z"), utc("2023-01-18T09:00:00Z")]);
+    }
+
+    #[test]
+    fn exdates_should_count_towards_count() {
+        let rule: RecurrenceRule = "FREQ=DAILY;COUNT=3".parse().unwrap();
+        let ret = rule.occurrences(utc("2023-01-01T09:00:00Z"), &[utc("2023-01-02T09:00:00Z")]).unwrap();
+        assert_eq!(ret, vec![utc("2023-01-01T09:00:00Z"), utc("2023-01-03T09:00:00Z")]);
+    }
+
+    #[test]
+    fn monthly_rule_should_skip_missing_days() {
+        let rule: RecurrenceRule = "FREQ=MONTHLY;COUNT=3".parse().unwrap();
+        let ret = rule.occurrences(utc("2023-01-31T09:00:00Z"), &[]).unwrap();
+        assert_eq!(ret, vec![utc("2023-01-31T09:00:00Z"), utc("2023-03-31T09:00:00Z"), utc("2023-05-31T09:00:00Z")]);
+    }
+
+    #[test]
+    fn until_date_should_include_the_whole_day() {
+        let rule: RecurrenceRule = "FREQ=YEARLY;UNTIL=20250101".parse().unwrap();
+        let ret = rule.occurrences(utc("2023-01-01T20:00:00Z"), &[]).unwrap();
+        assert_eq!(ret.len(), 3);
+    }
+
+    #[test]
+    fn occurrences_should_keep_local_time_across_dst() {
+        let rule: RecurrenceRule = "FREQ=WEEKLY;COUNT=3".parse().unwrap();
+        let dtstart = chrono_tz::Europe::Berlin.with_ymd_and_hms(2023, 3, 19, 10, 0, 0).unwrap();
+        let ret = rule.occurrences(dtstart, &[]).unwrap();
+        // CET is UTC+1 and CEST, from March 26th, UTC+2
+        assert_eq!(ret, vec![utc("2023-03-19T09:00:00Z"), utc("2023-03-26T08:00:00Z"), utc("2023-04-02T08:00:00Z")]);
+
+        // 02:30 doesn't exist on March 26th, it's taken as 03:30 CEST
+        let dtstart = chrono_tz::Europe::Berlin.with_ymd_and_hms(2023, 3, 25, 2, 30, 0).unwrap();
+        let ret = rule.occurrences(dtstart, &[]).unwrap();
+        assert_eq!(ret[..2], [utc("2023-03-25T01:30:00Z"), utc("2023-04-01T00:30:00Z")]);
+        let rule: RecurrenceRule = "FREQ=DAILY;COUNT=2".parse().unwrap();
+        let ret = rule.occurrences(dtstart, &[]).unwrap();
+        assert_eq!(ret, vec![utc("2023-03-25T01:30:00Z"), utc("2023-03-26T01:30:00Z")]);
+    }
+
+    #[test]
+    fn invalid_rules_should_be_rejected() {
+        for rule in ["FREQ=WEEKLY", "FREQ=HOURLY;COUNT=1", "FREQ=DAILY;COUNT=0", "COUNT=2",
+            "FREQ=DAILY;COUNT=2;UNTIL=20230101", "FREQ=MONTHLY;BYDAY=1MO;COUNT=2", "FREQ=DAILY;BYMONTH=1;COUNT=2"] {
+            assert!(matches!(rule.parse::<RecurrenceRule>(), Err(Error::InvalidRecurrenceRule(_))), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn too_many_occurrences_should_be_rejected() {
+        let rule: RecurrenceRule = "FREQ=DAILY;UNTIL=20991231".parse().unwrap();
+        let err = rule.occurrences(utc("2023-01-01T09:00:00Z"), &[]).unwrap_err();
+        assert!(matches!(err, Error::InvalidRecurrenceRule(_)));
+    }
+
+    #[test]
+    fn rule_should_display_as_rrule() {
+        let rule: RecurrenceRule = "freq=weekly;byday=tu,th;interval=2;until=20231231".parse().unwrap();
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;UNTIL=20231231T235959Z;BYDAY=TU,TH");
+        assert_eq!(rule.to_string().parse::<RecurrenceRule>().unwrap(), rule);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{RescheduleRequest, Reservation};
+use crate::error::Error;
+
+impl RescheduleRequest {
+    /// the reservation moved as requested, fields left empty in the request are kept
+    pub fn apply(&self, mut rsvp: Reservation) -> Result<Reservation, Error> {
+        if !self.resource_id.is_empty() {
+            rsvp.resource_id = self.resource_id.clone();
+        }
+        if self.start.is_some() {
+            rsvp.start = self.start.clone();
+        }
+        if self.end.is_some() {
+            rsvp.end = self.end.clone();
+        }
+        rsvp.validate()?;
+
+        Ok(rsvp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_should_keep_fields_left_empty() {
+        let rsvp = Reservation::new_pending("gyg", "room-1",
+                                            "2023-01-03T10:00:00Z".parse().unwrap(),
+                                            "2023-01-03T11:00:00Z".parse().unwrap(), "");
+        let request = RescheduleRequest { end: Some("2023-01-03T12:00:00Z".parse().unwrap()), ..Default::default() };
+
+        let moved = request.apply(rsvp.clone()).unwrap();
+        assert_eq!(moved.resource_id, "room-1");
+        assert_eq!(moved.start, rsvp.start);
+        assert_eq!(moved.end, request.end);
+
+        let request = RescheduleRequest { end: Some("2023-01-03T09:00:00Z".parse().unwrap()), ..Default::default() };
+        assert!(matches!(request.apply(rsvp), Err(Error::InvalidTime)));
+    }
+}
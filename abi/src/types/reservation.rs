@@ -1,12 +1,12 @@
 use std::ops::{Bound, Range};
-use chrono::{DateTime, FixedOffset, Utc};
+use chrono::{DateTime, Duration, FixedOffset, Utc};
 use sqlx::{FromRow, Row};
 use sqlx::postgres::PgRow;
 use sqlx::postgres::types::PgRange;
 use sqlx::types::Uuid;
-use crate::{Reservation, ReservationStatus, RsvpStatus};
+use crate::{Reservation, ReservationStatus, ReserveRequest, RsvpStatus};
 use crate::error::Error;
-use crate::utils::{convert_to_timestamp, convert_to_utc};
+use crate::utils::{convert_to_timestamp, convert_to_utc, to_interval};
 
 impl Reservation {
     pub fn new_pending(
@@ -24,6 +24,13 @@ impl Reservation {
             end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
             note: note.into(),
             status: ReservationStatus::Pending as i32,
+            series_id: "".to_string(),
+            expires_at: None,
+            cancelled_at: None,
+            cancelled_by: "".to_string(),
+            cancel_reason: "".to_string(),
+            version: 0,
+            bundle_id: "".to_string(),
         }
     }
 
@@ -58,9 +65,31 @@ impl Reservation {
     }
 }
 
-struct NaiveRange<T> {
-    start: Option<T>,
-    end: Option<T>,
+impl ReserveRequest {
+    /// how long a hold lasts before it's released, to the microsecond. None if the reservation isn't a hold
+    pub fn get_hold(&self) -> Result<Option<Duration>, Error> {
+        match &self.hold {
+            None => Ok(None),
+            Some(hold) => match to_interval(hold) {
+                Some(d) if d > Duration::zero() => Ok(Some(d)),
+                _ => Err(Error::InvalidHoldDuration(hold.seconds)),
+            },
+        }
+    }
+
+    /// none if the request shouldn't be deduplicated
+    pub fn get_idempotency_key(&self) -> Result<Option<&str>, Error> {
+        match self.idempotency_key.as_str() {
+            "" => Ok(None),
+            key if key.len() > 64 => Err(Error::InvalidIdempotencyKey(key.to_string())),
+            key => Ok(Some(key)),
+        }
+    }
+}
+
+pub(crate) struct NaiveRange<T> {
+    pub start: Option<T>,
+    pub end: Option<T>,
 }
 
 impl<T> From<PgRange<T>> for NaiveRange<T> {
@@ -94,6 +123,14 @@ impl FromRow<'_, PgRow> for Reservation {
 
         let id: Uuid = row.get("id");
         let status: RsvpStatus = row.get("status");
+        let series_id: Option<Uuid> = row.get("series_id");
+        let expires_at: Option<DateTime<Utc>> = row.get("expires_at");
+        let cancelled_at: Option<DateTime<Utc>> = row.get("cancelled_at");
+        let cancelled_by: Option<String> = row.get("cancelled_by");
+        let cancel_reason: Option<String> = row.get("cancel_reason");
+        // snapshots in the history made before versioning have none
+        let version: Option<i64> = row.get("version");
+        let bundle_id: Option<Uuid> = row.get("bundle_id");
         Ok(Self {
             id: id.to_string(),
             user_id: row.get("user_id"),
@@ -102,6 +139,13 @@ impl FromRow<'_, PgRow> for Reservation {
             start: Some(convert_to_timestamp(&start)),
             end: Some(convert_to_timestamp(&end)),
             note: row.get("note"),
+            series_id: series_id.map(|id| id.to_string()).unwrap_or_default(),
+            expires_at: expires_at.as_ref().map(convert_to_timestamp),
+            cancelled_at: cancelled_at.as_ref().map(convert_to_timestamp),
+            cancelled_by: cancelled_by.unwrap_or_default(),
+            cancel_reason: cancel_reason.unwrap_or_default(),
+            version: version.unwrap_or_default(),
+            bundle_id: bundle_id.map(|id| id.to_string()).unwrap_or_default(),
         })
     }
 }
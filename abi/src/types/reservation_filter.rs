@@ -0,0 +1,45 @@
+use crate::{ReservationFilter, ReservationFilterBuilder, ReservationStatus};
+use crate::error::Error;
+
+/// largest page a single filter call could return
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+impl ReservationFilterBuilder {
+    pub fn build(&self) -> Result<ReservationFilter, Error> {
+        let filter = self.private_build()
+            .expect("failed to build ReservationFilter");
+
+        filter.validate()?;
+
+        Ok(filter)
+    }
+}
+
+impl ReservationFilter {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.page_size < 1 || self.page_size > MAX_PAGE_SIZE {
+            return Err(Error::InvalidPageSize(self.page_size));
+        }
+
+        if let Some(cursor) = self.cursor {
+            if cursor < 0 {
+                return Err(Error::InvalidCursor(cursor));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// status to filter by, None if every status should be returned
+    pub fn get_status(&self) -> Option<ReservationStatus> {
+        match ReservationStatus::from_i32(self.status) {
+            None | Some(ReservationStatus::Unknown) => None,
+            status => status,
+        }
+    }
+
+    /// cursor pointing before the first reservation in the sort direction
+    pub fn first_cursor(&self) -> i64 {
+        if self.desc { i64::MAX } else { 0 }
+    }
+}
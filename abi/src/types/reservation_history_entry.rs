@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, Row};
+use sqlx::postgres::PgRow;
+use sqlx::types::Uuid;
+use crate::{ReservationHistoryEntry, ReservationUpdateType, RsvpUpdateType};
+use crate::utils::convert_to_timestamp;
+
+/// snapshots are left empty, they're stored as json and decoded separately
+impl FromRow<'_, PgRow> for ReservationHistoryEntry {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let reservation_id: Uuid = row.get("reservation_id");
+        let op: RsvpUpdateType = row.get("op");
+        let actor: Option<String> = row.get("actor");
+        let changed_at: DateTime<Utc> = row.get("changed_at");
+
+        Ok(Self {
+            id: row.get("id"),
+            reservation_id: reservation_id.to_string(),
+            op: ReservationUpdateType::from(op) as i32,
+            actor: actor.unwrap_or_default(),
+            before: None,
+            after: None,
+            changed_at: Some(convert_to_timestamp(&changed_at)),
+        })
+    }
+}
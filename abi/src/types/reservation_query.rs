@@ -0,0 +1,59 @@
+use std::ops::Bound;
+use chrono::{DateTime, Utc};
+use prost_types::Timestamp;
+use sqlx::postgres::types::PgRange;
+use crate::{ReservationQuery, ReservationQueryBuilder, ReservationStatus};
+use crate::error::Error;
+use crate::utils::convert_to_utc;
+
+impl ReservationQueryBuilder {
+    pub fn build(&self) -> Result<ReservationQuery, Error> {
+        let query = self.private_build()
+            .expect("failed to build ReservationQuery");
+
+        query.validate()?;
+
+        Ok(query)
+    }
+}
+
+impl ReservationQuery {
+    pub fn validate(&self) -> Result<(), Error> {
+        if let (Some(start), Some(end)) = (to_bound_time(&self.start)?, to_bound_time(&self.end)?) {
+            if start >= end {
+                return Err(Error::InvalidTime);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// time range of the query, missing start or end means unbounded
+    pub fn get_timespan(&self) -> Result<PgRange<DateTime<Utc>>, Error> {
+        let f = |v: Option<DateTime<Utc>>| match v {
+            Some(v) => Bound::Included(v),
+            None => Bound::Unbounded,
+        };
+
+        Ok(PgRange {
+            start: f(to_bound_time(&self.start)?),
+            end: f(to_bound_time(&self.end)?),
+        })
+    }
+
+    /// status to filter by, None if every status should be returned
+    pub fn get_status(&self) -> Option<ReservationStatus> {
+        match ReservationStatus::from_i32(self.status) {
+            None | Some(ReservationStatus::Unknown) => None,
+            status => status,
+        }
+    }
+}
+
+// an empty timestamp (None or 0) means infinity
+fn to_bound_time(ts: &Option<Timestamp>) -> Result<Option<DateTime<Utc>>, Error> {
+    match ts {
+        Some(t) if t.seconds != 0 || t.nanos != 0 => Ok(Some(convert_to_utc(ts)?)),
+        _ => Ok(None),
+    }
+}
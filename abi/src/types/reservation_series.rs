@@ -0,0 +1,186 @@
+use std::ops::Range;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use chrono_tz::Tz;
+use sqlx::{FromRow, Row};
+use sqlx::postgres::PgRow;
+use sqlx::postgres::types::PgRange;
+use sqlx::types::Uuid;
+
+use crate::{CancelSeriesRequest, RecurrenceRule, ReservationSeries, ReservationStatus, RsvpStatus, UpdateSeriesRequest};
+use crate::error::Error;
+use crate::types::reservation::NaiveRange;
+use crate::utils::{convert_to_timestamp, convert_to_utc};
+
+impl ReservationSeries {
+    pub fn new_pending(
+        uid: impl Into<String>,
+        rid: impl Into<String>,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+        rrule: impl Into<String>,
+        note: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: "".to_string(),
+            user_id: uid.into(),
+            status: ReservationStatus::Pending as i32,
+            resource_id: rid.into(),
+            start: Some(convert_to_timestamp(&start.with_timezone(&Utc))),
+            end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
+            note: note.into(),
+            rrule: rrule.into(),
+            exdates: vec![],
+            time_zone: "".to_string(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.user_id.is_empty() {
+            return Err(Error::InvalidUserId(self.user_id.clone()));
+        }
+
+        if self.resource_id.is_empty() {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+
+        let timespan = self.get_timespan()?;
+        if timespan.start >= timespan.end {
+            return Err(Error::InvalidTime);
+        }
+
+        self.get_rule()?;
+        self.get_exdates()?;
+        self.get_time_zone()?;
+
+        Ok(())
+    }
+
+    /// time window of the first occurrence
+    pub fn get_timespan(&self) -> Result<Range<DateTime<Utc>>, Error> {
+        let start = convert_to_utc(&self.start)?;
+        let end = convert_to_utc(&self.end)?;
+
+        Ok(Range { start, end })
+    }
+
+    pub fn get_rule(&self) -> Result<RecurrenceRule, Error> {
+        self.rrule.parse()
+    }
+
+    pub fn get_exdates(&self) -> Result<Vec<DateTime<Utc>>, Error> {
+        self.exdates.iter()
+            .map(|ts| convert_to_utc(&Some(ts.clone())))
+            .collect()
+    }
+
+    pub fn get_time_zone(&self) -> Result<Tz, Error> {
+        if self.time_zone.is_empty() {
+            return Ok(Tz::UTC);
+        }
+        self.time_zone.parse().map_err(|_| Error::InvalidTimeZone(self.time_zone.clone()))
+    }
+
+    /// time windows of all occurrences of the series, in order
+    pub fn occurrences(&self) -> Result<Vec<Range<DateTime<Utc>>>, Error> {
+        let first = self.get_timespan()?;
+        let duration = first.end - first.start;
+        let dtstart = first.start.with_timezone(&self.get_time_zone()?);
+
+        Ok(self.get_rule()?
+            .occurrences(dtstart, &self.get_exdates()?)?
+            .into_iter()
+            .map(|start| start..start + duration)
+            .collect())
+    }
+}
+
+impl UpdateSeriesRequest {
+    pub fn get_from(&self) -> Result<DateTime<Utc>, Error> {
+        convert_to_utc(&self.from)
+    }
+}
+
+impl CancelSeriesRequest {
+    /// none if the whole series should be cancelled
+    pub fn get_from(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        self.from.as_ref()
+            .map(|ts| convert_to_utc(&Some(ts.clone())))
+            .transpose()
+    }
+}
+
+impl FromRow<'_, PgRow> for ReservationSeries {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let range: PgRange<DateTime<Utc>> = row.get("timespan");
+        let range: NaiveRange<DateTime<Utc>> = range.into();
+        let (start, end) = range.start.zip(range.end).ok_or_else(|| sqlx::Error::ColumnDecode {
+            index: "timespan".to_string(),
+            source: "unbounded timespan".into(),
+        })?;
+
+        let id: Uuid = row.get("id");
+        let status: RsvpStatus = row.get("status");
+        let exdates: Vec<DateTime<Utc>> = row.get("exdates");
+        Ok(Self {
+            id: id.to_string(),
+            user_id: row.get("user_id"),
+            status: ReservationStatus::from(status) as i32,
+            resource_id: row.get("resource_id"),
+            start: Some(convert_to_timestamp(&start)),
+            end: Some(convert_to_timestamp(&end)),
+            note: row.get("note"),
+            rrule: row.get("rrule"),
+            exdates: exdates.iter().map(convert_to_timestamp).collect(),
+            time_zone: row.get("time_zone"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn series_should_expand_to_occurrences() {
+        let series = ReservationSeries::new_pending("gyg", "room-1",
+                                                    "2023-01-03T10:00:00+0800".parse().unwrap(),
+                                                    "2023-01-03T11:00:00+0800".parse().unwrap(),
+                                                    "FREQ=WEEKLY;BYDAY=TU;COUNT=52", "");
+        series.validate().unwrap();
+
+        let occurrences = series.occurrences().unwrap();
+        assert_eq!(occurrences.len(), 52);
+        assert_eq!(occurrences[51].start.to_rfc3339(), "2023-12-26T02:00:00+00:00");
+        assert_eq!(occurrences[51].end.to_rfc3339(), "2023-12-26T03:00:00+00:00");
+    }
+
+    #[test]
+    fn series_should_expand_in_its_time_zone() {
+        let series = ReservationSeries {
+            time_zone: "America/New_York".to_string(),
+            ..ReservationSeries::new_pending("gyg", "room-1",
+                                             "2023-03-06T09:00:00-0500".parse().unwrap(),
+                                             "2023-03-06T10:00:00-0500".parse().unwrap(),
+                                             "FREQ=WEEKLY;COUNT=2", "")
+        };
+        series.validate().unwrap();
+
+        // still 9 o'clock in New York after DST starts on March 12th
+        let occurrences = series.occurrences().unwrap();
+        assert_eq!(occurrences[1].start.to_rfc3339(), "2023-03-13T13:00:00+00:00");
+        assert_eq!(occurrences[1].end.to_rfc3339(), "2023-03-13T14:00:00+00:00");
+
+        let series = ReservationSeries { time_zone: "Mars/Olympus_Mons".to_string(), ..series };
+        assert!(matches!(series.validate(), Err(Error::InvalidTimeZone(_))));
+    }
+
+    #[test]
+    fn series_with_invalid_rule_should_fail() {
+        let series = ReservationSeries::new_pending("gyg", "room-1",
+                                                    "2023-01-03T10:00:00+0800".parse().unwrap(),
+                                                    "2023-01-03T11:00:00+0800".parse().unwrap(),
+                                                    "FREQ=WEEKLY", "");
+        assert!(matches!(series.validate(), Err(Error::InvalidRecurrenceRule(_))));
+    }
+}
@@ -1,6 +1,33 @@
 use std::fmt;
 use std::fmt::Formatter;
 use crate::{ReservationStatus, RsvpStatus};
+use crate::error::Error;
+
+impl ReservationStatus {
+    /// statuses a reservation in this status could move to
+    pub fn next_statuses(&self) -> &'static [ReservationStatus] {
+        match self {
+            ReservationStatus::Pending => &[ReservationStatus::Confirmed, ReservationStatus::Rejected, ReservationStatus::Cancelled],
+            ReservationStatus::Confirmed => &[ReservationStatus::Cancelled],
+            ReservationStatus::Blocked => &[ReservationStatus::Released],
+            _ => &[],
+        }
+    }
+
+    /// reservations in a final status don't hold their time window
+    pub fn is_final(&self) -> bool {
+        matches!(self, ReservationStatus::Cancelled | ReservationStatus::Rejected | ReservationStatus::Released)
+    }
+
+    /// check the transition to status `to` is allowed
+    pub fn transition(self, to: ReservationStatus) -> Result<ReservationStatus, Error> {
+        if self.next_statuses().contains(&to) {
+            Ok(to)
+        } else {
+            Err(Error::InvalidTransition { from: self, to })
+        }
+    }
+}
 
 impl fmt::Display for ReservationStatus {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -9,6 +36,9 @@ impl fmt::Display for ReservationStatus {
             ReservationStatus::Pending => write!(f, "pending"),
             ReservationStatus::Confirmed => write!(f, "confirmed"),
             ReservationStatus::Blocked => write!(f, "blocked"),
+            ReservationStatus::Cancelled => write!(f, "cancelled"),
+            ReservationStatus::Rejected => write!(f, "rejected"),
+            ReservationStatus::Released => write!(f, "released"),
         }
     }
 }
@@ -20,6 +50,32 @@ impl From<RsvpStatus> for ReservationStatus {
             RsvpStatus::Pending => ReservationStatus::Pending,
             RsvpStatus::Confirmed => ReservationStatus::Confirmed,
             RsvpStatus::Blocked => ReservationStatus::Blocked,
+            RsvpStatus::Cancelled => ReservationStatus::Cancelled,
+            RsvpStatus::Rejected => ReservationStatus::Rejected,
+            RsvpStatus::Released => ReservationStatus::Released,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn allowed_transitions_should_pass() {
+        use ReservationStatus::*;
+        for (from, to) in [(Pending, Confirmed), (Pending, Rejected), (Pending, Cancelled), (Confirmed, Cancelled), (Blocked, Released)] {
+            assert_eq!(from.transition(to).unwrap(), to);
+        }
+    }
+
+    #[test]
+    fn other_transitions_should_be_refused() {
+        use ReservationStatus::*;
+        for (from, to) in [(Confirmed, Pending), (Confirmed, Confirmed), (Cancelled, Confirmed), (Blocked, Cancelled), (Released, Blocked), (Unknown, Pending)] {
+            let err = from.transition(to).unwrap_err();
+            assert!(matches!(err, Error::InvalidTransition { from: f, to: t } if f == from && t == to));
+        }
+        assert_eq!(Confirmed.transition(Pending).unwrap_err().to_string(), "invalid transition from confirmed to pending");
+    }
+}
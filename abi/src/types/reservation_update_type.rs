@@ -0,0 +1,14 @@
+use crate::{ReservationUpdateType, RsvpUpdateType};
+
+impl From<RsvpUpdateType> for ReservationUpdateType {
+    fn from(op: RsvpUpdateType) -> Self {
+        match op {
+            RsvpUpdateType::Unknown => ReservationUpdateType::Unknown,
+            RsvpUpdateType::Create => ReservationUpdateType::Create,
+            RsvpUpdateType::Update => ReservationUpdateType::Update,
+            RsvpUpdateType::Delete => ReservationUpdateType::Delete,
+            RsvpUpdateType::Expire => ReservationUpdateType::Expire,
+            RsvpUpdateType::Promote => ReservationUpdateType::Promote,
+        }
+    }
+}
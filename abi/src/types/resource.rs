@@ -0,0 +1,218 @@
+use chrono::{Duration, NaiveDate};
+use sqlx::{FromRow, Row};
+use sqlx::postgres::PgRow;
+use sqlx::postgres::types::PgInterval;
+use crate::{OpeningHours, Resource, RsvpOpeningHours, RsvpSlotRounding, SlotRounding, Weekday};
+use crate::error::Error;
+use crate::utils::{convert_interval, convert_to_duration};
+
+impl Resource {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, resource_type: impl Into<String>, capacity: i32) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            resource_type: resource_type.into(),
+            capacity,
+            active: true,
+            buffer_before: None,
+            buffer_after: None,
+            time_zone: "".to_string(),
+            opening_hours: vec![],
+            holidays: vec![],
+            slot: None,
+            min_duration: None,
+            max_duration: None,
+            rounding: SlotRounding::Reject as i32,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() || self.id.len() > 64 {
+            return Err(Error::InvalidResourceId(self.id.clone()));
+        }
+
+        if self.capacity < 1 {
+            return Err(Error::InvalidCapacity(self.capacity));
+        }
+
+        self.get_buffers()?;
+        self.get_opening_hours()?;
+        self.get_holidays()?;
+
+        // negative durations are rejected while converting them
+        let (slot, min_duration, max_duration) = self.get_slot_limits()?;
+        // slots are counted from midnight, so every day has to start on a boundary
+        let day = Duration::days(1).num_microseconds().unwrap_or_default();
+        if !slot.is_zero() && slot.num_microseconds().is_none_or(|slot| day % slot != 0) {
+            return Err(Error::InvalidSlot(format!("a slot of {}s doesn't divide a day", slot.num_seconds())));
+        }
+        if !max_duration.is_zero() && min_duration > max_duration {
+            return Err(Error::InvalidDuration(min_duration.num_seconds()));
+        }
+
+        Ok(())
+    }
+
+    /// slot, min and max duration of reservations, zero if unset
+    pub fn get_slot_limits(&self) -> Result<(Duration, Duration, Duration), Error> {
+        Ok((
+            convert_to_duration(&self.slot)?,
+            convert_to_duration(&self.min_duration)?,
+            convert_to_duration(&self.max_duration)?,
+        ))
+    }
+
+    pub fn get_rounding(&self) -> RsvpSlotRounding {
+        SlotRounding::from_i32(self.rounding)
+            .unwrap_or(SlotRounding::Reject)
+            .into()
+    }
+
+    /// opening hours as stored in postgres, each has to be a non-empty span of one day
+    pub fn get_opening_hours(&self) -> Result<Vec<RsvpOpeningHours>, Error> {
+        self.opening_hours.iter().map(|hours| {
+            let invalid = || Error::InvalidCalendar(format!("{:?}", hours));
+            let weekday = match Weekday::from_i32(hours.weekday) {
+                Some(Weekday::Unknown) | None => return Err(invalid()),
+                Some(weekday) => weekday as i16,
+            };
+            let opens_at = convert_to_duration(&hours.opens_at)?;
+            let closes_at = convert_to_duration(&hours.closes_at)?;
+            if opens_at >= closes_at || closes_at > Duration::days(1) {
+                return Err(invalid());
+            }
+
+            Ok(RsvpOpeningHours {
+                weekday,
+                opens_at: PgInterval::try_from(opens_at).map_err(|_| invalid())?,
+                closes_at: PgInterval::try_from(closes_at).map_err(|_| invalid())?,
+            })
+        }).collect()
+    }
+
+    pub fn get_holidays(&self) -> Result<Vec<NaiveDate>, Error> {
+        self.holidays.iter()
+            .map(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d")
+                .map_err(|_| Error::InvalidCalendar(day.clone())))
+            .collect()
+    }
+
+    /// buffers kept free before and after each reservation, zero if unset
+    pub fn get_buffers(&self) -> Result<(Duration, Duration), Error> {
+        Ok((convert_to_duration(&self.buffer_before)?, convert_to_duration(&self.buffer_after)?))
+    }
+}
+
+impl FromRow<'_, PgRow> for Resource {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let opening_hours: Vec<RsvpOpeningHours> = row.get("opening_hours");
+        let holidays: Vec<NaiveDate> = row.get("holidays");
+        let rounding: RsvpSlotRounding = row.get("rounding");
+
+        Ok(Self {
+            id: row.get("id"),
+            name: row.get("name"),
+            resource_type: row.get("resource_type"),
+            capacity: row.get("capacity"),
+            active: row.get("active"),
+            buffer_before: convert_interval(row.get("buffer_before")),
+            buffer_after: convert_interval(row.get("buffer_after")),
+            time_zone: row.get("time_zone"),
+            opening_hours: opening_hours.into_iter().map(|hours| OpeningHours {
+                weekday: hours.weekday as i32,
+                opens_at: convert_interval(hours.opens_at),
+                closes_at: convert_interval(hours.closes_at),
+            }).collect(),
+            holidays: holidays.iter().map(|day| day.format("%Y-%m-%d").to_string()).collect(),
+            slot: convert_interval(row.get("slot")),
+            min_duration: convert_interval(row.get("min_duration")),
+            max_duration: convert_interval(row.get("max_duration")),
+            rounding: SlotRounding::from(rounding) as i32,
+        })
+    }
+}
+
+impl From<SlotRounding> for RsvpSlotRounding {
+    fn from(rounding: SlotRounding) -> Self {
+        match rounding {
+            SlotRounding::Reject => RsvpSlotRounding::Reject,
+            SlotRounding::Outward => RsvpSlotRounding::Outward,
+            SlotRounding::Nearest => RsvpSlotRounding::Nearest,
+        }
+    }
+}
+
+impl From<RsvpSlotRounding> for SlotRounding {
+    fn from(rounding: RsvpSlotRounding) -> Self {
+        match rounding {
+            RsvpSlotRounding::Reject => SlotRounding::Reject,
+            RsvpSlotRounding::Outward => SlotRounding::Outward,
+            RsvpSlotRounding::Nearest => SlotRounding::Nearest,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn negative_buffer_should_be_rejected() {
+        let resource = Resource {
+            buffer_after: Some(prost_types::Duration { seconds: -60, nanos: 0 }),
+            ..Resource::new("room-1", "Ocean", "hotel-room", 1)
+        };
+        assert!(matches!(resource.validate(), Err(Error::InvalidDuration(-60))));
+
+        let resource = Resource {
+            buffer_after: Some(prost_types::Duration { seconds: 7200, nanos: 0 }),
+            ..resource
+        };
+        assert_eq!(resource.get_buffers().unwrap(), (Duration::zero(), Duration::hours(2)));
+    }
+
+    #[test]
+    fn invalid_slot_limits_should_be_rejected() {
+        let minutes = |m: i64| Some(prost_types::Duration { seconds: m * 60, nanos: 0 });
+        let resource = Resource {
+            slot: minutes(15),
+            min_duration: minutes(30),
+            max_duration: minutes(120),
+            ..Resource::new("court-1", "", "tennis-court", 1)
+        };
+        resource.validate().unwrap();
+
+        assert!(matches!(Resource { slot: minutes(-15), ..resource.clone() }.validate(), Err(Error::InvalidDuration(-900))));
+        assert!(matches!(Resource { min_duration: minutes(-30), ..resource.clone() }.validate(), Err(Error::InvalidDuration(_))));
+        assert!(matches!(Resource { max_duration: minutes(-120), ..resource.clone() }.validate(), Err(Error::InvalidDuration(_))));
+        assert!(matches!(Resource { min_duration: minutes(180), ..resource.clone() }.validate(), Err(Error::InvalidDuration(10800))));
+
+        // 7 minute slots would start at a different time every day
+        assert!(matches!(Resource { slot: minutes(7), ..resource.clone() }.validate(), Err(Error::InvalidSlot(_))));
+        assert!(matches!(Resource { slot: minutes(25 * 60), ..resource.clone() }.validate(), Err(Error::InvalidSlot(_))));
+        Resource { slot: minutes(24 * 60), ..resource }.validate().unwrap();
+    }
+
+    #[test]
+    fn invalid_calendar_should_be_rejected() {
+        let hours = |weekday: Weekday, opens_at: i64, closes_at: i64| OpeningHours {
+            weekday: weekday as i32,
+            opens_at: Some(prost_types::Duration { seconds: opens_at * 3600, nanos: 0 }),
+            closes_at: Some(prost_types::Duration { seconds: closes_at * 3600, nanos: 0 }),
+        };
+        let resource = Resource {
+            opening_hours: vec![hours(Weekday::Monday, 8, 20), hours(Weekday::Sunday, 0, 24)],
+            holidays: vec!["2023-01-06".to_string()],
+            ..Resource::new("room-1", "Ocean", "meeting-room", 1)
+        };
+        resource.validate().unwrap();
+        assert_eq!(resource.get_opening_hours().unwrap()[1].weekday, 7);
+
+        let closed = Resource { opening_hours: vec![hours(Weekday::Monday, 20, 8)], ..resource.clone() };
+        assert!(matches!(closed.validate(), Err(Error::InvalidCalendar(_))));
+        let unknown = Resource { opening_hours: vec![hours(Weekday::Unknown, 8, 20)], ..resource.clone() };
+        assert!(matches!(unknown.validate(), Err(Error::InvalidCalendar(_))));
+        let holiday = Resource { holidays: vec!["06.01.2023".to_string()], ..resource };
+        assert!(matches!(holiday.validate(), Err(Error::InvalidCalendar(day)) if day == "06.01.2023"));
+    }
+}
@@ -0,0 +1,79 @@
+use std::ops::Range;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{FromRow, Row};
+use sqlx::postgres::PgRow;
+use sqlx::postgres::types::PgRange;
+use sqlx::types::Uuid;
+use crate::WaitlistEntry;
+use crate::error::Error;
+use crate::types::reservation::NaiveRange;
+use crate::utils::{convert_interval, convert_to_timestamp, convert_to_utc, to_interval};
+
+impl WaitlistEntry {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.user_id.is_empty() {
+            return Err(Error::InvalidUserId(self.user_id.clone()));
+        }
+
+        if self.resource_id.is_empty() {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+
+        if self.start.is_none() || self.end.is_none() {
+            return Err(Error::InvalidTime);
+        }
+
+        let timespan = self.get_timespan()?;
+        if timespan.start >= timespan.end {
+            return Err(Error::InvalidTime);
+        }
+
+        self.get_hold()?;
+
+        Ok(())
+    }
+
+    /// how long the reservation the entry is promoted to is held, an hour if unset
+    pub fn get_hold(&self) -> Result<Duration, Error> {
+        match &self.hold {
+            None => Ok(Duration::hours(1)),
+            // checked once truncated to the microsecond, a shorter hold would be released right away
+            Some(hold) => match to_interval(hold) {
+                Some(d) if d > Duration::zero() => Ok(d),
+                _ => Err(Error::InvalidHoldDuration(hold.seconds)),
+            },
+        }
+    }
+
+    pub fn get_timespan(&self) -> Result<Range<DateTime<Utc>>, Error> {
+        let start = convert_to_utc(&self.start)?;
+        let end = convert_to_utc(&self.end)?;
+
+        Ok(Range { start, end })
+    }
+}
+
+impl FromRow<'_, PgRow> for WaitlistEntry {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let range: PgRange<DateTime<Utc>> = row.get("timespan");
+        let range: NaiveRange<DateTime<Utc>> = range.into();
+
+        let id: Uuid = row.get("id");
+        let note: Option<String> = row.get("note");
+        let created_at: DateTime<Utc> = row.get("created_at");
+        let reservation_id: Option<Uuid> = row.get("reservation_id");
+
+        Ok(Self {
+            id: id.to_string(),
+            user_id: row.get("user_id"),
+            resource_id: row.get("resource_id"),
+            start: range.start.as_ref().map(convert_to_timestamp),
+            end: range.end.as_ref().map(convert_to_timestamp),
+            note: note.unwrap_or_default(),
+            created_at: Some(convert_to_timestamp(&created_at)),
+            reservation_id: reservation_id.map(|id| id.to_string()).unwrap_or_default(),
+            hold: convert_interval(row.get("hold")),
+        })
+    }
+}
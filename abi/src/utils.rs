@@ -1,5 +1,6 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use prost_types::Timestamp;
+use sqlx::postgres::types::PgInterval;
 use crate::error::Error;
 
 pub fn convert_to_utc(ts: &Option<Timestamp>) -> Result<DateTime<Utc>, Error> {
@@ -8,12 +9,9 @@ pub fn convert_to_utc(ts: &Option<Timestamp>) -> Result<DateTime<Utc>, Error> {
     }
     let ts = ts.as_ref().unwrap();
 
-    Ok(DateTime::<Utc>::from_utc(
-        NaiveDateTime::from_timestamp(
-            ts.seconds,
-            ts.nanos as _),
-        Utc,
-    ))
+    Utc.timestamp_opt(ts.seconds, ts.nanos as _)
+        .single()
+        .ok_or(Error::InvalidTime)
 }
 
 pub fn convert_to_timestamp(dt: &DateTime<Utc>) -> Timestamp {
@@ -22,3 +20,34 @@ pub fn convert_to_timestamp(dt: &DateTime<Utc>) -> Timestamp {
         nanos: dt.timestamp_subsec_nanos() as _,
     }
 }
+
+/// unset is zero, postgres keeps intervals to the microsecond
+pub fn convert_to_duration(d: &Option<prost_types::Duration>) -> Result<Duration, Error> {
+    match d {
+        None => Ok(Duration::zero()),
+        Some(d) => to_interval(d).ok_or(Error::InvalidDuration(d.seconds)),
+    }
+}
+
+/// the duration truncated to the microsecond, none if it's negative or too long to be bound as an interval
+pub(crate) fn to_interval(d: &prost_types::Duration) -> Option<Duration> {
+    if d.seconds < 0 || d.nanos < 0 {
+        return None;
+    }
+    let d = Duration::try_seconds(d.seconds)?.checked_add(&Duration::microseconds(d.nanos as i64 / 1000))?;
+    // sqlx counts the interval in nanoseconds when encoding it
+    d.num_nanoseconds().map(|_| d)
+}
+
+/// a zero interval is reported as unset
+pub fn convert_interval(interval: PgInterval) -> Option<prost_types::Duration> {
+    let micros = interval.microseconds + (interval.days as i64 + interval.months as i64 * 30) * 86_400_000_000;
+    if micros == 0 {
+        return None;
+    }
+
+    Some(prost_types::Duration {
+        seconds: micros / 1_000_000,
+        nanos: (micros % 1_000_000) as i32 * 1000,
+    })
+}
@@ -0,0 +1,24 @@
+-- replace the first version of query, which ignored status and sort direction
+drop function rsvp.query(text, text, tstzrange);
+
+-- query reservations overlapping with `during`, null uid/rid/st means no filter
+create or replace function rsvp.query(uid text,
+                                      rid text,
+                                      during tstzrange,
+                                      st rsvp.reservation_status,
+                                      is_desc bool default false)
+    returns setof rsvp.reservations
+as
+$$
+BEGIN
+    return query select *
+                 from rsvp.reservations r
+                 where (uid is null or r.user_id = uid)
+                   and (rid is null or r.resource_id = rid)
+                   and (st is null or r.status = st)
+                   and r.timespan && during
+                 order by case when is_desc then lower(r.timespan) end desc,
+                          case when not is_desc then lower(r.timespan) end,
+                          r.id;
+END;
+$$ language plpgsql;
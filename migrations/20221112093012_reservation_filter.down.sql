@@ -0,0 +1,3 @@
+DROP FUNCTION rsvp.filter;
+DROP INDEX rsvp.reservations_seq_idx;
+ALTER TABLE rsvp.reservations DROP COLUMN seq;
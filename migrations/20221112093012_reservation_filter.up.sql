@@ -0,0 +1,27 @@
+-- monotonic sequence of reservations, used as the key of cursor based pagination
+alter table rsvp.reservations add column seq bigserial not null;
+create unique index reservations_seq_idx on rsvp.reservations (seq);
+
+-- filter reservations by keyset pagination on seq.
+-- return at most page_size reservations after (asc) or before (desc) cur, null cur means from the beginning
+create or replace function rsvp.filter(uid text,
+                                       rid text,
+                                       st rsvp.reservation_status,
+                                       cur bigint default null,
+                                       is_desc bool default false,
+                                       page_size bigint default 10)
+    returns setof rsvp.reservations
+as
+$$
+BEGIN
+    return query select *
+                 from rsvp.reservations r
+                 where (uid is null or r.user_id = uid)
+                   and (rid is null or r.resource_id = rid)
+                   and (st is null or r.status = st)
+                   and (cur is null or (is_desc and r.seq < cur) or (not is_desc and r.seq > cur))
+                 order by case when is_desc then r.seq end desc,
+                          case when not is_desc then r.seq end
+                 limit page_size;
+END;
+$$ language plpgsql;
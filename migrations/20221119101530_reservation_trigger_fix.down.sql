@@ -0,0 +1,18 @@
+ALTER TABLE rsvp.reservation_changes DROP CONSTRAINT reservation_changes_pkey;
+
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'insert' then
+        insert into rsvp.reservation_changes(reservation_id, op) values (NEW.id, 'create');
+    elsif TG_OP = 'update' then
+        if OLD.status <> NEW.status then
+            insert into rsvp.reservation_changes(reservation_id, op) values (NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'delete' then
+        insert into rsvp.reservation_changes(reservation_id, op) values (OLD.id, 'delete');
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
@@ -0,0 +1,19 @@
+-- TG_OP is always upper case, the first version of the trigger never recorded any change
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        insert into rsvp.reservation_changes(reservation_id, op) values (NEW.id, 'create');
+    elsif TG_OP = 'UPDATE' then
+        if OLD.status <> NEW.status then
+            insert into rsvp.reservation_changes(reservation_id, op) values (NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'DELETE' then
+        insert into rsvp.reservation_changes(reservation_id, op) values (OLD.id, 'delete');
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
+
+alter table rsvp.reservation_changes add constraint reservation_changes_pkey primary key (id);
@@ -0,0 +1,30 @@
+drop trigger reservation_trigger on rsvp.reservations;
+create trigger reservation_trigger
+    after insert or update or delete
+    on rsvp.reservations
+    for each row
+execute procedure rsvp.reservation_trigger();
+
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        insert into rsvp.reservation_changes(reservation_id, op) values (NEW.id, 'create');
+    elsif TG_OP = 'UPDATE' then
+        if OLD.status <> NEW.status then
+            insert into rsvp.reservation_changes(reservation_id, op) values (NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'DELETE' then
+        insert into rsvp.reservation_changes(reservation_id, op) values (OLD.id, 'delete');
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
+
+drop function rsvp.record_change(uuid, text);
+
+DROP TABLE rsvp.reservation_consumers;
+
+ALTER SEQUENCE rsvp.reservation_changes_id_seq AS integer;
+ALTER TABLE rsvp.reservation_changes ALTER COLUMN id TYPE integer;
@@ -0,0 +1,49 @@
+-- reservation_changes becomes an append-only log, consumers keep their own offset
+alter table rsvp.reservation_changes alter column id type bigint;
+alter sequence rsvp.reservation_changes_id_seq as bigint;
+
+-- last change id delivered to each named consumer of the change feed
+create table rsvp.reservation_consumers
+(
+    name           varchar(64) not null,
+    last_change_id bigint      not null,
+    updated_at     timestamptz not null default now(),
+    constraint reservation_consumers_pkey primary key (name)
+);
+
+-- append a change to the feed. Change ids have to become visible in order, or a reader could skip a change
+-- committed later with a smaller id, so writers take turns from here until they commit.
+-- The change is text, so later migrations can recreate rsvp.reservation_update_type
+create or replace function rsvp.record_change(rsvp_id uuid, change text) returns void as
+$$
+begin
+    perform pg_advisory_xact_lock('rsvp.reservation_changes'::regclass::oid::bigint);
+    insert into rsvp.reservation_changes(reservation_id, op) values (rsvp_id, change::rsvp.reservation_update_type);
+end;
+$$ language plpgsql;
+
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        perform rsvp.record_change(NEW.id, 'create');
+    elsif TG_OP = 'UPDATE' then
+        if OLD.status <> NEW.status then
+            perform rsvp.record_change(NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'DELETE' then
+        perform rsvp.record_change(OLD.id, 'delete');
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
+
+-- changes are recorded at commit, so writers only take turns for as long as it takes to commit
+drop trigger reservation_trigger on rsvp.reservations;
+create constraint trigger reservation_trigger
+    after insert or update or delete
+    on rsvp.reservations
+    deferrable initially deferred
+    for each row
+execute procedure rsvp.reservation_trigger();
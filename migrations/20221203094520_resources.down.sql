@@ -0,0 +1,12 @@
+DROP TRIGGER reservation_capacity_check ON rsvp.reservations;
+DROP FUNCTION rsvp.reservation_capacity_check();
+DROP FUNCTION rsvp.raise_reservation_conflict(rsvp.reservations, rsvp.reservations, integer);
+DROP FUNCTION rsvp.is_exclusive(rsvp.reservations);
+DROP FUNCTION rsvp.holds_window(rsvp.reservations);
+DROP FUNCTION rsvp.pad(tstzrange, interval, interval);
+DROP FUNCTION rsvp.buffers(text);
+DROP INDEX rsvp.reservations_resource_timespan_idx;
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_resource_id_fkey;
+ALTER TABLE rsvp.reservations
+    ADD CONSTRAINT reservations_conflict EXCLUDE USING gist (resource_id WITH =, timespan WITH &&);
+DROP TABLE rsvp.resources;
@@ -0,0 +1,147 @@
+-- resources which could be reserved
+create table rsvp.resources
+(
+    id            varchar(64) not null,
+    name          text        not null default '',
+    resource_type varchar(64) not null default '',
+    capacity      integer     not null default 1,
+    active        boolean     not null default true,
+    created_at    timestamptz not null default now(),
+    constraint resources_pkey primary key (id),
+    constraint resources_capacity_check check (capacity > 0)
+);
+
+-- register resources already reserved, with the capacity the exclusion constraint enforced
+insert into rsvp.resources (id, name)
+select distinct resource_id, resource_id
+from rsvp.reservations;
+
+-- a resource could have up to capacity overlapping reservations, which can't be expressed by
+-- the exclusion constraint, rsvp.reservation_capacity_check enforces it instead
+alter table rsvp.reservations drop constraint reservations_conflict;
+alter table rsvp.reservations
+    add constraint reservations_resource_id_fkey foreign key (resource_id) references rsvp.resources (id);
+create index reservations_resource_timespan_idx on rsvp.reservations using gist (resource_id, timespan);
+
+-- the time kept free on a resource before and after each reservation, nothing so far
+create or replace function rsvp.buffers(rid text, out pre interval, out post interval) as
+$$
+select '0'::interval, '0'::interval;
+$$ language sql stable;
+
+-- a timespan widened by the buffers around it
+create or replace function rsvp.pad(span tstzrange, pre interval, post interval) returns tstzrange as
+$$
+select tstzrange(lower(span) - pre, upper(span) + post);
+$$ language sql immutable;
+
+-- whether a reservation still holds its window, every reservation does so far
+create or replace function rsvp.holds_window(r rsvp.reservations) returns boolean as
+$$
+select true;
+$$ language sql stable;
+
+-- whether a reservation takes the whole resource no matter its capacity, none does so far
+create or replace function rsvp.is_exclusive(r rsvp.reservations) returns boolean as
+$$
+select false;
+$$ language sql stable;
+
+-- same error as the exclusion constraint reported, with the capacity exceeded as hint
+create or replace function rsvp.raise_reservation_conflict(new_rsvp rsvp.reservations, existing rsvp.reservations, cap integer)
+    returns void as
+$$
+begin
+    raise exception 'conflicting key value violates capacity of resource %', new_rsvp.resource_id
+        using errcode = 'exclusion_violation',
+            schema = 'rsvp',
+            table = 'reservations',
+            detail = format('Key (resource_id, timespan)=(%s, %s) conflicts with existing key (resource_id, timespan)=(%s, %s).',
+                            new_rsvp.resource_id, new_rsvp.timespan, existing.resource_id, existing.timespan),
+            hint = format('capacity: %s', cap);
+end;
+$$ language plpgsql;
+
+-- reservations are compared by their padded timespans, two of them overlap if they are less than
+-- buffer_after + buffer_before apart. Conflicts still report the timespans the customers booked.
+-- Later migrations change what's compared by replacing the helpers above, not this function
+create or replace function rsvp.reservation_capacity_check() returns trigger as
+$$
+declare
+    cap       integer;
+    is_active boolean;
+    peak      timestamptz;
+    booked    bigint;
+    existing  rsvp.reservations;
+    pre       interval;
+    post      interval;
+begin
+    -- lock the resource, so reservations of the same resource are checked one at a time
+    select capacity, active into cap, is_active from rsvp.resources where id = NEW.resource_id for update;
+    if not found then
+        raise exception 'unknown resource: %', NEW.resource_id using errcode = 'RV001', detail = NEW.resource_id;
+    end if;
+    if not is_active then
+        raise exception 'inactive resource: %', NEW.resource_id using errcode = 'RV002', detail = NEW.resource_id;
+    end if;
+
+    if not rsvp.holds_window(NEW) then
+        return NEW;
+    end if;
+    select b.pre, b.post into pre, post from rsvp.buffers(NEW.resource_id) b;
+
+    -- nothing overlaps an exclusive reservation
+    select *
+    into existing
+    from rsvp.reservations r
+    where r.resource_id = NEW.resource_id
+      and r.id <> NEW.id
+      and rsvp.pad(r.timespan, pre, post) && rsvp.pad(NEW.timespan, pre, post)
+      and rsvp.holds_window(r)
+      and (rsvp.is_exclusive(NEW) or rsvp.is_exclusive(r))
+    order by lower(r.timespan), r.id
+    limit 1;
+    if found then
+        perform rsvp.raise_reservation_conflict(NEW, existing, cap);
+    end if;
+
+    -- the number of overlapping reservations only grows when one of them starts,
+    -- so the busiest moment is the start of one of the overlapping reservations, buffer included
+    select p.t, count(r.id)
+    into peak, booked
+    from (select greatest(lower(o.timespan), lower(NEW.timespan)) - pre as t
+          from rsvp.reservations o
+          where o.resource_id = NEW.resource_id
+            and o.id <> NEW.id
+            and rsvp.pad(o.timespan, pre, post) && rsvp.pad(NEW.timespan, pre, post)
+            and rsvp.holds_window(o)) p
+             join rsvp.reservations r
+                  on r.resource_id = NEW.resource_id and r.id <> NEW.id and rsvp.pad(r.timespan, pre, post) @> p.t
+                      and rsvp.holds_window(r)
+    group by p.t
+    order by count(r.id) desc, p.t
+    limit 1;
+
+    if booked >= cap then
+        select *
+        into existing
+        from rsvp.reservations r
+        where r.resource_id = NEW.resource_id
+          and r.id <> NEW.id
+          and rsvp.pad(r.timespan, pre, post) @> peak
+          and rsvp.holds_window(r)
+        order by lower(r.timespan), r.id
+        limit 1;
+
+        perform rsvp.raise_reservation_conflict(NEW, existing, cap);
+    end if;
+
+    return NEW;
+end;
+$$ language plpgsql;
+
+create trigger reservation_capacity_check
+    before insert or update of resource_id, timespan
+    on rsvp.reservations
+    for each row
+execute procedure rsvp.reservation_capacity_check();
@@ -0,0 +1,2 @@
+alter table rsvp.reservations drop column series_id;
+drop table rsvp.reservation_series;
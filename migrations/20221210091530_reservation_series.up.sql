@@ -0,0 +1,27 @@
+-- recurring series of reservations, its occurrences are expanded into rsvp.reservations
+create table rsvp.reservation_series
+(
+    id          uuid                    not null default gen_random_uuid(),
+    user_id     varchar(64)             not null,
+    status      rsvp.reservation_status not null default 'pending',
+    resource_id varchar(64)             not null,
+    -- window of the first occurrence (DTSTART), every occurrence lasts as long
+    timespan    tstzrange               not null,
+    note        text                    not null default '',
+    -- RFC 5545 RRULE value
+    rrule       text                    not null,
+    -- start of the occurrences excluded from the series (EXDATE)
+    exdates     timestamptz[]           not null default '{}',
+    -- IANA time zone the occurrences are expanded in (TZID), empty for UTC
+    time_zone   varchar(64)             not null default '',
+    created_at  timestamptz             not null default now(),
+    constraint reservation_series_pkey primary key (id),
+    constraint reservation_series_resource_id_fkey foreign key (resource_id) references rsvp.resources (id)
+);
+
+alter table rsvp.reservations
+    add column series_id uuid null;
+alter table rsvp.reservations
+    add constraint reservations_series_id_fkey foreign key (series_id)
+        references rsvp.reservation_series (id) on delete set null;
+create index reservations_series_id_idx on rsvp.reservations (series_id);
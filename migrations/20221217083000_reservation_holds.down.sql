@@ -0,0 +1,33 @@
+-- restore the functions of 20221126083045_reservation_consumers and 20221203094520_resources
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        perform rsvp.record_change(NEW.id, 'create');
+    elsif TG_OP = 'UPDATE' then
+        if OLD.status <> NEW.status then
+            perform rsvp.record_change(NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'DELETE' then
+        perform rsvp.record_change(OLD.id, 'delete');
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
+
+create or replace function rsvp.holds_window(r rsvp.reservations) returns boolean as
+$$
+select true;
+$$ language sql stable;
+
+-- enum values can't be dropped, recreate the type without 'expire'
+alter type rsvp.reservation_update_type rename to reservation_update_type_old;
+create type rsvp.reservation_update_type as enum ('unknown', 'create', 'update', 'delete');
+alter table rsvp.reservation_changes
+    alter column op type rsvp.reservation_update_type
+        using (case when op = 'expire' then 'delete' else op::text end)::rsvp.reservation_update_type;
+drop type rsvp.reservation_update_type_old;
+
+drop index rsvp.reservations_expires_at_idx;
+alter table rsvp.reservations drop column expires_at;
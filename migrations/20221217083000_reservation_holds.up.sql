@@ -0,0 +1,34 @@
+-- pending reservations could be holds, released unless confirmed before expires_at
+alter table rsvp.reservations
+    add column expires_at timestamptz null;
+create index reservations_expires_at_idx on rsvp.reservations (expires_at) where expires_at is not null;
+
+-- a released hold is recorded as 'expire' instead of 'delete'
+alter type rsvp.reservation_update_type add value 'expire';
+
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        perform rsvp.record_change(NEW.id, 'create');
+    elsif TG_OP = 'UPDATE' then
+        if OLD.status <> NEW.status then
+            perform rsvp.record_change(NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'DELETE' then
+        if OLD.status = 'pending' and OLD.expires_at <= now() then
+            perform rsvp.record_change(OLD.id, 'expire');
+        else
+            perform rsvp.record_change(OLD.id, 'delete');
+        end if;
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
+
+-- expired holds don't take capacity, even before the reaper releases them
+create or replace function rsvp.holds_window(r rsvp.reservations) returns boolean as
+$$
+select r.expires_at is null or r.expires_at > now();
+$$ language sql stable;
@@ -0,0 +1,5 @@
+-- restore the function of 20221203094520_resources
+create or replace function rsvp.is_exclusive(r rsvp.reservations) returns boolean as
+$$
+select false;
+$$ language sql stable;
@@ -0,0 +1,5 @@
+-- a blocked reservation takes the whole resource no matter its capacity
+create or replace function rsvp.is_exclusive(r rsvp.reservations) returns boolean as
+$$
+select r.status = 'blocked';
+$$ language sql stable;
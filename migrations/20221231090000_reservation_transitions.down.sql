@@ -0,0 +1,8 @@
+-- restore the function of 20221217083000_reservation_holds
+create or replace function rsvp.holds_window(r rsvp.reservations) returns boolean as
+$$
+select r.expires_at is null or r.expires_at > now();
+$$ language sql stable;
+
+-- enum values can't be dropped, and rsvp.query and rsvp.filter depend on the type,
+-- so the final states are left in rsvp.reservation_status
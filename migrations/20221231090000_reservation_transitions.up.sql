@@ -0,0 +1,12 @@
+-- final states of the reservation state machine
+alter type rsvp.reservation_status add value if not exists 'cancelled';
+alter type rsvp.reservation_status add value if not exists 'rejected';
+alter type rsvp.reservation_status add value if not exists 'released';
+
+-- whether a reservation still holds its window: reservations in a final state and expired holds don't.
+-- new enum values can't be used before commit, compare status as text
+create or replace function rsvp.holds_window(r rsvp.reservations) returns boolean as
+$$
+select r.status::text not in ('cancelled', 'rejected', 'released')
+           and (r.expires_at is null or r.expires_at > now());
+$$ language sql stable;
@@ -0,0 +1,5 @@
+alter table rsvp.reservations drop constraint reservations_cancelled_check;
+alter table rsvp.reservations
+    drop column cancelled_at,
+    drop column cancelled_by,
+    drop column cancel_reason;
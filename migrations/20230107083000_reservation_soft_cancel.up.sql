@@ -0,0 +1,7 @@
+-- reservations are cancelled instead of deleted, keeping who cancelled them and why
+alter table rsvp.reservations
+    add column cancelled_at  timestamptz null,
+    add column cancelled_by  varchar(64) null,
+    add column cancel_reason text        null;
+alter table rsvp.reservations
+    add constraint reservations_cancelled_check check (status <> 'cancelled' or cancelled_at is not null);
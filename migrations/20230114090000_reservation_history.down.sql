@@ -0,0 +1,3 @@
+drop trigger reservation_history_trigger on rsvp.reservations;
+drop function rsvp.reservation_history_trigger();
+drop table rsvp.reservation_history;
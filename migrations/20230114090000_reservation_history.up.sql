@@ -0,0 +1,45 @@
+-- audit log of every change made to a reservation
+create table rsvp.reservation_history
+(
+    id             bigserial                    not null,
+    reservation_id uuid                         not null,
+    op             rsvp.reservation_update_type not null,
+    -- who made the change, set by `set_config('rsvp.actor', ..., true)` in the transaction
+    actor          varchar(64)                  null,
+    -- snapshots of the reservation, before is null on insert and after is null on delete
+    before         jsonb                        null,
+    after          jsonb                        null,
+    changed_at     timestamptz                  not null default now(),
+    constraint reservation_history_pkey primary key (id)
+);
+
+create index reservation_history_reservation_id_idx on rsvp.reservation_history (reservation_id, id);
+
+create or replace function rsvp.reservation_history_trigger() returns trigger as
+$$
+declare
+    actor varchar(64) := nullif(current_setting('rsvp.actor', true), '');
+begin
+    if TG_OP = 'INSERT' then
+        insert into rsvp.reservation_history(reservation_id, op, actor, after)
+        values (NEW.id, 'create', actor, to_jsonb(NEW));
+    elsif TG_OP = 'UPDATE' then
+        if OLD is distinct from NEW then
+            insert into rsvp.reservation_history(reservation_id, op, actor, before, after)
+            values (NEW.id, 'update', actor, to_jsonb(OLD), to_jsonb(NEW));
+        end if;
+    elsif TG_OP = 'DELETE' then
+        insert into rsvp.reservation_history(reservation_id, op, actor, before)
+        values (OLD.id,
+                case when OLD.status = 'pending' and OLD.expires_at <= now() then 'expire' else 'delete' end::rsvp.reservation_update_type,
+                actor, to_jsonb(OLD));
+    end if;
+    return NULL;
+end;
+$$ language plpgsql;
+
+create trigger reservation_history_trigger
+    after insert or update or delete
+    on rsvp.reservations
+    for each row
+execute procedure rsvp.reservation_history_trigger();
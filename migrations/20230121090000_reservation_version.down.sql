@@ -0,0 +1,3 @@
+drop trigger reservation_version_trigger on rsvp.reservations;
+drop function rsvp.reservation_version_trigger();
+alter table rsvp.reservations drop column version;
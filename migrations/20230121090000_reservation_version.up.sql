@@ -0,0 +1,16 @@
+-- version of a reservation, bumped on every update so stale writes could be detected
+alter table rsvp.reservations add column version bigint not null default 1;
+
+create or replace function rsvp.reservation_version_trigger() returns trigger as
+$$
+begin
+    NEW.version := OLD.version + 1;
+    return NEW;
+end;
+$$ language plpgsql;
+
+create trigger reservation_version_trigger
+    before update
+    on rsvp.reservations
+    for each row
+execute procedure rsvp.reservation_version_trigger();
@@ -0,0 +1,3 @@
+alter table rsvp.reservations drop constraint reservations_idempotency_key;
+alter table rsvp.reservations drop column idempotency_request;
+alter table rsvp.reservations drop column idempotency_key;
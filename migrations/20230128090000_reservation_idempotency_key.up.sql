@@ -0,0 +1,5 @@
+-- key of the reserve request that made the reservation, retries with the same key get the same reservation
+alter table rsvp.reservations add column idempotency_key varchar(64) null;
+-- md5 of the resource, window and note asked for with the key, a retry has to ask for the same
+alter table rsvp.reservations add column idempotency_request char(32) null;
+alter table rsvp.reservations add constraint reservations_idempotency_key unique (user_id, idempotency_key);
@@ -0,0 +1,21 @@
+-- moving a reservation to another window or resource is a change listeners care about as well
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        perform rsvp.record_change(NEW.id, 'create');
+    elsif TG_OP = 'UPDATE' then
+        if OLD.status <> NEW.status or OLD.resource_id <> NEW.resource_id or OLD.timespan <> NEW.timespan then
+            perform rsvp.record_change(NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'DELETE' then
+        if OLD.status = 'pending' and OLD.expires_at <= now() then
+            perform rsvp.record_change(OLD.id, 'expire');
+        else
+            perform rsvp.record_change(OLD.id, 'delete');
+        end if;
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
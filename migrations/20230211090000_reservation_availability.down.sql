@@ -0,0 +1 @@
+drop function rsvp.availability(text, tstzrange, interval, boolean);
@@ -0,0 +1,49 @@
+-- free intervals of a resource within during that last at least min_duration. An interval is free while
+-- the resource has capacity left and isn't blocked, pending reservations only take capacity if include_pending
+create or replace function rsvp.availability(rid text, during tstzrange, min_duration interval, include_pending boolean)
+    returns setof tstzrange as
+$$
+declare
+    cap       integer;
+    is_active boolean;
+    gap       interval;
+begin
+    select capacity, active into cap, is_active from rsvp.resources where id = rid;
+    if not found then
+        raise exception 'unknown resource: %', rid using errcode = 'RV001', detail = rid;
+    end if;
+    -- inactive resources can't be reserved, nothing is free
+    if not is_active then
+        return;
+    end if;
+    -- a reservation could only start buffer_after + buffer_before after another one ends
+    select bf.pre + bf.post into gap from rsvp.buffers(rid) bf;
+
+    return query
+        with busy as (select rsvp.pad(r.timespan, gap, gap) * during as timespan, r.status = 'blocked' as blocked
+                      from rsvp.reservations r
+                      where r.resource_id = rid
+                        and rsvp.pad(r.timespan, gap, gap) && during
+                        and rsvp.holds_window(r)
+                        and (include_pending or r.status <> 'pending')),
+             -- split the window at every start and end of a reservation,
+             -- each segment is then either fully covered by a reservation or not at all
+             points as (select lower(during) as t
+                        union
+                        select upper(during)
+                        union
+                        select lower(b.timespan) from busy b
+                        union
+                        select upper(b.timespan) from busy b),
+             segments as (select tstzrange(p.t, lead(p.t) over (order by p.t)) as segment from points p),
+             free as (select s.segment
+                      from segments s
+                      where upper(s.segment) is not null
+                        and not exists(select 1 from busy b where b.blocked and b.timespan && s.segment)
+                        and (select count(*) from busy b where b.timespan && s.segment) < cap)
+        select slot
+        from unnest((select range_agg(f.segment) from free f)) slot
+        where upper(slot) - lower(slot) >= min_duration
+        order by lower(slot);
+end;
+$$ language plpgsql stable;
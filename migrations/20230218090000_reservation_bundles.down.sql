@@ -0,0 +1,2 @@
+drop index rsvp.reservations_bundle_id_idx;
+alter table rsvp.reservations drop column bundle_id;
@@ -0,0 +1,4 @@
+-- reservations made together in one bundle share its id, so they could be confirmed or cancelled together
+alter table rsvp.reservations add column bundle_id uuid null;
+
+create index reservations_bundle_id_idx on rsvp.reservations (bundle_id);
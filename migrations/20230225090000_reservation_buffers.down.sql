@@ -0,0 +1,10 @@
+-- restore the function of 20221203094520_resources
+create or replace function rsvp.buffers(rid text, out pre interval, out post interval) as
+$$
+select '0'::interval, '0'::interval;
+$$ language sql stable;
+
+alter table rsvp.resources
+    drop constraint resources_buffer_check,
+    drop column buffer_before,
+    drop column buffer_after;
@@ -0,0 +1,12 @@
+-- time kept free around each reservation of a resource, e.g. to clean a hotel room between guests
+alter table rsvp.resources
+    add column buffer_before interval not null default '0',
+    add column buffer_after  interval not null default '0',
+    add constraint resources_buffer_check check (buffer_before >= '0' and buffer_after >= '0');
+
+-- the capacity check and rsvp.availability compare reservations by their padded timespans,
+-- two of them overlap if they are less than buffer_after + buffer_before apart
+create or replace function rsvp.buffers(rid text, out pre interval, out post interval) as
+$$
+select buffer_before, buffer_after from rsvp.resources where id = rid;
+$$ language sql stable;
@@ -0,0 +1,53 @@
+drop trigger reservation_waitlist_trigger on rsvp.reservations;
+drop function rsvp.reservation_waitlist_trigger();
+drop function rsvp.promote_waitlist(text, tstzrange);
+
+-- restore the functions of 20230204090000_reservation_reschedule and 20230114090000_reservation_history
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        perform rsvp.record_change(NEW.id, 'create');
+    elsif TG_OP = 'UPDATE' then
+        if OLD.status <> NEW.status or OLD.resource_id <> NEW.resource_id or OLD.timespan <> NEW.timespan then
+            perform rsvp.record_change(NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'DELETE' then
+        if OLD.status = 'pending' and OLD.expires_at <= now() then
+            perform rsvp.record_change(OLD.id, 'expire');
+        else
+            perform rsvp.record_change(OLD.id, 'delete');
+        end if;
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
+
+create or replace function rsvp.reservation_history_trigger() returns trigger as
+$$
+declare
+    actor varchar(64) := nullif(current_setting('rsvp.actor', true), '');
+begin
+    if TG_OP = 'INSERT' then
+        insert into rsvp.reservation_history(reservation_id, op, actor, after)
+        values (NEW.id, 'create', actor, to_jsonb(NEW));
+    elsif TG_OP = 'UPDATE' then
+        if OLD is distinct from NEW then
+            insert into rsvp.reservation_history(reservation_id, op, actor, before, after)
+            values (NEW.id, 'update', actor, to_jsonb(OLD), to_jsonb(NEW));
+        end if;
+    elsif TG_OP = 'DELETE' then
+        insert into rsvp.reservation_history(reservation_id, op, actor, before)
+        values (OLD.id,
+                case when OLD.status = 'pending' and OLD.expires_at <= now() then 'expire' else 'delete' end::rsvp.reservation_update_type,
+                actor, to_jsonb(OLD));
+    end if;
+    return NULL;
+end;
+$$ language plpgsql;
+
+drop function rsvp.is_promotion(uuid);
+drop table rsvp.waitlist;
+
+-- enum values can't be dropped, 'promote' is kept but no longer recorded
@@ -0,0 +1,131 @@
+-- a reservation made by promoting a waitlist entry is recorded as 'promote' instead of 'create'
+alter type rsvp.reservation_update_type add value if not exists 'promote';
+
+-- users waiting for a window of a fully booked resource
+create table rsvp.waitlist
+(
+    id             uuid        not null default gen_random_uuid(),
+    user_id        varchar(64) not null,
+    resource_id    varchar(64) not null,
+    timespan       tstzrange   not null,
+    note           text        null,
+    created_at     timestamptz not null default now(),
+    -- how long the reservation the entry is promoted to is held before it's released unless confirmed
+    hold           interval    not null default '1 hour',
+    -- the pending reservation the entry was promoted to, null while waiting
+    reservation_id uuid        null,
+    constraint waitlist_pkey primary key (id)
+);
+
+create index waitlist_resource_id_idx on rsvp.waitlist (resource_id, created_at) where reservation_id is null;
+create unique index waitlist_reservation_id_idx on rsvp.waitlist (reservation_id);
+
+-- turn the entries waiting for a window overlapping during into pending holds, first come first served.
+-- Entries which still don't fit, or break a rule of the resource, keep waiting
+create or replace function rsvp.promote_waitlist(rid text, during tstzrange) returns void as
+$$
+declare
+    gap     interval;
+    entry   rsvp.waitlist;
+    rsvp_id uuid;
+begin
+    -- entries kept apart by the buffers of the resource may fit as well
+    select buffer_before + buffer_after into gap from rsvp.resources where id = rid;
+
+    for entry in select *
+                 from rsvp.waitlist w
+                 where w.resource_id = rid
+                   and w.reservation_id is null
+                   and upper(w.timespan) > now()
+                   and w.timespan && rsvp.pad(during, coalesce(gap, '0'), coalesce(gap, '0'))
+                 order by w.created_at, w.id
+                 for update
+        loop
+            begin
+                -- link the entry first, so the reservation is known to be a promotion once inserted
+                rsvp_id := gen_random_uuid();
+                update rsvp.waitlist set reservation_id = rsvp_id where id = entry.id;
+                insert into rsvp.reservations (id, user_id, resource_id, timespan, note, status, expires_at)
+                values (rsvp_id, entry.user_id, entry.resource_id, entry.timespan, entry.note, 'pending', now() + entry.hold);
+            exception
+                -- a taken window or any rule of the resource (RV...) keeps the entry waiting
+                when others then
+                    if sqlstate <> '23P01' and sqlstate not like 'RV%' then
+                        raise;
+                    end if;
+            end;
+        end loop;
+end;
+$$ language plpgsql;
+
+-- the window of a reservation is freed once it's cancelled, released, expired or moved elsewhere
+create or replace function rsvp.reservation_waitlist_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'DELETE' then
+        perform rsvp.promote_waitlist(OLD.resource_id, OLD.timespan);
+    elsif rsvp.holds_window(OLD) and (not rsvp.holds_window(NEW)
+        or OLD.resource_id <> NEW.resource_id or OLD.timespan <> NEW.timespan) then
+        perform rsvp.promote_waitlist(OLD.resource_id, OLD.timespan);
+    end if;
+    return NULL;
+end;
+$$ language plpgsql;
+
+create trigger reservation_waitlist_trigger
+    after update of status, resource_id, timespan or delete
+    on rsvp.reservations
+    for each row
+execute procedure rsvp.reservation_waitlist_trigger();
+
+-- whether the reservation was made by promoting a waitlist entry
+create or replace function rsvp.is_promotion(rsvp_id uuid) returns boolean as
+$$
+select exists(select 1 from rsvp.waitlist w where w.reservation_id = rsvp_id);
+$$ language sql stable;
+
+-- promotions are surfaced in the change feed and the history
+create or replace function rsvp.reservation_trigger() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        perform rsvp.record_change(NEW.id, case when rsvp.is_promotion(NEW.id) then 'promote' else 'create' end);
+    elsif TG_OP = 'UPDATE' then
+        if OLD.status <> NEW.status or OLD.resource_id <> NEW.resource_id or OLD.timespan <> NEW.timespan then
+            perform rsvp.record_change(NEW.id, 'update');
+        end if;
+    elsif TG_OP = 'DELETE' then
+        if OLD.status = 'pending' and OLD.expires_at <= now() then
+            perform rsvp.record_change(OLD.id, 'expire');
+        else
+            perform rsvp.record_change(OLD.id, 'delete');
+        end if;
+    end if;
+    notify reservation_update;
+    return NULL;
+end;
+$$ language plpgsql;
+
+create or replace function rsvp.reservation_history_trigger() returns trigger as
+$$
+declare
+    actor varchar(64) := nullif(current_setting('rsvp.actor', true), '');
+begin
+    if TG_OP = 'INSERT' then
+        insert into rsvp.reservation_history(reservation_id, op, actor, after)
+        values (NEW.id, case when rsvp.is_promotion(NEW.id) then 'promote' else 'create' end::rsvp.reservation_update_type,
+                actor, to_jsonb(NEW));
+    elsif TG_OP = 'UPDATE' then
+        if OLD is distinct from NEW then
+            insert into rsvp.reservation_history(reservation_id, op, actor, before, after)
+            values (NEW.id, 'update', actor, to_jsonb(OLD), to_jsonb(NEW));
+        end if;
+    elsif TG_OP = 'DELETE' then
+        insert into rsvp.reservation_history(reservation_id, op, actor, before)
+        values (OLD.id,
+                case when OLD.status = 'pending' and OLD.expires_at <= now() then 'expire' else 'delete' end::rsvp.reservation_update_type,
+                actor, to_jsonb(OLD));
+    end if;
+    return NULL;
+end;
+$$ language plpgsql;
@@ -0,0 +1,6 @@
+drop trigger reservation_policy_check on rsvp.reservations;
+drop function rsvp.reservation_policy_check();
+drop function rsvp.raise_policy_violation(text, text);
+drop function rsvp.local_time(text, timestamptz);
+
+drop table rsvp.policies;
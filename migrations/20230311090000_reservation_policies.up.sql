@@ -0,0 +1,106 @@
+-- limits on the reservations users could make, checked by rsvp.reservation_policy_check
+-- whenever a reservation is written. Zero means no limit
+create table rsvp.policies
+(
+    -- '' for the policy for every resource
+    resource_id             varchar(64) not null,
+    max_active_reservations integer     not null default 0,
+    max_duration            interval    not null default '0',
+    min_lead_time           interval    not null default '0',
+    max_horizon             interval    not null default '0',
+    -- reservations have to fall within opens_at and closes_at of a day, counted from midnight in the time zone
+    -- of the resource (rsvp.local_time). A closes_at of 0 is the end of the day, reservations are not limited
+    -- to a day if both are 0
+    opens_at                interval    not null default '0',
+    closes_at               interval    not null default '0',
+    updated_at              timestamptz not null default now(),
+    constraint policies_pkey primary key (resource_id)
+);
+
+-- the wall clock time of a resource at t, UTC as resources have no time zone so far
+create or replace function rsvp.local_time(rid text, t timestamptz) returns timestamp as
+$$
+select t at time zone 'UTC';
+$$ language sql stable;
+
+-- report a broken rule of a policy, hint is the rule and detail why it's broken
+create or replace function rsvp.raise_policy_violation(rule text, reason text) returns void as
+$$
+begin
+    raise exception 'policy violation: %', rule
+        using errcode = 'RV005', hint = rule, detail = reason;
+end;
+$$ language plpgsql;
+
+-- a reservation has to satisfy the policy of its resource and the policy for every resource, whoever writes it.
+-- Triggers of the same event fire in alphabetical order, this one sees the timespan aligned to slots
+create or replace function rsvp.reservation_policy_check() returns trigger as
+$$
+declare
+    p          rsvp.policies;
+    active     bigint;
+    length     interval;
+    local_from timestamp;
+    local_to   timestamp;
+    midnight   timestamp;
+begin
+    -- blocks close a resource on purpose, they could be anywhere
+    if NEW.status = 'blocked' or not rsvp.holds_window(NEW) then
+        return NEW;
+    end if;
+
+    length := upper(NEW.timespan) - lower(NEW.timespan);
+    local_from := rsvp.local_time(NEW.resource_id, lower(NEW.timespan));
+    local_to := rsvp.local_time(NEW.resource_id, upper(NEW.timespan));
+    midnight := date_trunc('day', local_from);
+    for p in select * from rsvp.policies where resource_id in ('', NEW.resource_id) order by resource_id
+        loop
+            if p.max_active_reservations > 0 then
+                -- reservations of the same user are counted one at a time, so concurrent ones can't both take the last slot
+                perform pg_advisory_xact_lock(hashtext(NEW.user_id));
+                select count(*)
+                into active
+                from rsvp.reservations r
+                where r.user_id = NEW.user_id
+                  and (p.resource_id = '' or r.resource_id = p.resource_id)
+                  and r.id <> NEW.id
+                  and upper(r.timespan) > now()
+                  and rsvp.holds_window(r);
+                if active >= p.max_active_reservations then
+                    perform rsvp.raise_policy_violation('max_active_reservations',
+                        format('%s has %s active reservations, at most %s are allowed', NEW.user_id, active, p.max_active_reservations));
+                end if;
+            end if;
+
+            if p.max_duration > '0' and length > p.max_duration then
+                perform rsvp.raise_policy_violation('max_duration',
+                    format('reservations last %s at most', p.max_duration));
+            end if;
+
+            if p.min_lead_time > '0' and lower(NEW.timespan) < now() + p.min_lead_time then
+                perform rsvp.raise_policy_violation('min_lead_time',
+                    format('reservations have to be made %s before they start', p.min_lead_time));
+            end if;
+
+            if p.max_horizon > '0' and lower(NEW.timespan) > now() + p.max_horizon then
+                perform rsvp.raise_policy_violation('max_horizon',
+                    format('reservations could start %s ahead at most', p.max_horizon));
+            end if;
+
+            if (p.opens_at > '0' or p.closes_at > '0') and (local_from < midnight + p.opens_at
+                or local_to > midnight + coalesce(nullif(p.closes_at, '0'), '1 day')) then
+                perform rsvp.raise_policy_violation('allowed_hours',
+                    format('reservations have to be within %s and %s after midnight in the time zone of the resource',
+                           p.opens_at, coalesce(nullif(p.closes_at, '0'), '24:00')));
+            end if;
+        end loop;
+
+    return NEW;
+end;
+$$ language plpgsql;
+
+create trigger reservation_policy_check
+    before insert or update of resource_id, timespan
+    on rsvp.reservations
+    for each row
+execute procedure rsvp.reservation_policy_check();
@@ -0,0 +1,18 @@
+drop trigger waitlist_calendar_check on rsvp.waitlist;
+drop function rsvp.waitlist_calendar_check();
+drop trigger reservation_calendar_check on rsvp.reservations;
+drop function rsvp.reservation_calendar_check();
+drop function rsvp.check_calendar(rsvp.resources, tstzrange);
+
+-- restore the function of 20230311090000_reservation_policies
+create or replace function rsvp.local_time(rid text, t timestamptz) returns timestamp as
+$$
+select t at time zone 'UTC';
+$$ language sql stable;
+
+alter table rsvp.resources
+    drop column time_zone,
+    drop column opening_hours,
+    drop column holidays;
+
+drop type rsvp.opening_hours;
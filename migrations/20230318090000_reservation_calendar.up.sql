@@ -0,0 +1,116 @@
+-- a span of a weekday (ISO, monday is 1) a resource is open, offsets from midnight in the time zone of the resource
+create type rsvp.opening_hours as
+(
+    weekday   smallint,
+    opens_at  interval,
+    closes_at interval
+);
+
+-- when a resource could be reserved: opening hours (always if empty) and whole days it's closed,
+-- both in its time zone ('' is UTC)
+alter table rsvp.resources
+    add column time_zone     varchar(64)          not null default '',
+    add column opening_hours rsvp.opening_hours[] not null default '{}',
+    add column holidays      date[]               not null default '{}';
+
+-- policies count their allowed hours in the time zone of the resource too
+create or replace function rsvp.local_time(rid text, t timestamptz) returns timestamp as
+$$
+select t at time zone coalesce((select nullif(r.time_zone, '') from rsvp.resources r where r.id = rid), 'UTC');
+$$ language sql stable;
+
+-- a timespan has to fall within one span of opening hours of the resource and must not touch a holiday,
+-- raise RV003 saying when the resource is open otherwise
+create or replace function rsvp.check_calendar(res rsvp.resources, timespan tstzrange) returns void as
+$$
+declare
+    tz         text;
+    local_from timestamp;
+    local_to   timestamp;
+    holiday    date;
+    open_hours text;
+begin
+    tz := coalesce(nullif(res.time_zone, ''), 'UTC');
+    local_from := lower(timespan) at time zone tz;
+    local_to := upper(timespan) at time zone tz;
+
+    select d
+    into holiday
+    from unnest(res.holidays) d
+    where tsrange(d, d + 1) && tsrange(local_from, local_to)
+    order by d
+    limit 1;
+    if found then
+        raise exception 'outside of opening hours: %', res.id
+            using errcode = 'RV003',
+                detail = format('%s is closed on %s', res.id, holiday);
+    end if;
+
+    if cardinality(res.opening_hours) = 0 or exists(
+            select 1
+            from unnest(res.opening_hours) h
+            where h.weekday = extract(isodow from local_from)
+              and local_from >= local_from::date + h.opens_at
+              and local_to <= local_from::date + h.closes_at) then
+        return;
+    end if;
+
+    select string_agg(to_char(h.opens_at, 'HH24:MI') || '-' || to_char(h.closes_at, 'HH24:MI'), ', ' order by h.opens_at)
+    into open_hours
+    from unnest(res.opening_hours) h
+    where h.weekday = extract(isodow from local_from);
+    raise exception 'outside of opening hours: %', res.id
+        using errcode = 'RV003',
+            detail = case
+                         when open_hours is null then format('%s is closed on %s', res.id, trim(to_char(local_from, 'Day')))
+                         else format('%s is open %s on %s (%s)', res.id, open_hours, trim(to_char(local_from, 'Day')), tz)
+                end;
+end;
+$$ language plpgsql;
+
+-- reservations have to fit the calendar of their resource. Blocks close a resource on purpose, they could be anywhere
+create or replace function rsvp.reservation_calendar_check() returns trigger as
+$$
+declare
+    res rsvp.resources;
+begin
+    if NEW.status = 'blocked' or not rsvp.holds_window(NEW) then
+        return NEW;
+    end if;
+
+    -- unknown resources are reported by rsvp.reservation_capacity_check
+    select * into res from rsvp.resources where id = NEW.resource_id;
+    if found then
+        perform rsvp.check_calendar(res, NEW.timespan);
+    end if;
+
+    return NEW;
+end;
+$$ language plpgsql;
+
+create trigger reservation_calendar_check
+    before insert or update of resource_id, timespan
+    on rsvp.reservations
+    for each row
+execute procedure rsvp.reservation_calendar_check();
+
+-- an entry the calendar rules out would never be promoted
+create or replace function rsvp.waitlist_calendar_check() returns trigger as
+$$
+declare
+    res rsvp.resources;
+begin
+    select * into res from rsvp.resources where id = NEW.resource_id;
+    if found then
+        perform rsvp.check_calendar(res, NEW.timespan);
+    end if;
+
+    return NEW;
+end;
+$$ language plpgsql;
+
+create trigger waitlist_calendar_check
+    before insert or update of resource_id, timespan
+    on rsvp.waitlist
+    for each row
+execute procedure rsvp.waitlist_calendar_check();
@@ -0,0 +1,13 @@
+drop trigger waitlist_align_check on rsvp.waitlist;
+drop function rsvp.waitlist_align_check();
+drop trigger reservation_align_check on rsvp.reservations;
+drop function rsvp.reservation_align_check();
+drop function rsvp.align_timespan(rsvp.resources, tstzrange);
+
+alter table rsvp.resources
+    drop column slot,
+    drop column min_duration,
+    drop column max_duration,
+    drop column rounding;
+
+drop type rsvp.slot_rounding;
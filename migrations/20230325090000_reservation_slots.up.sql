@@ -0,0 +1,117 @@
+-- what to do with a reservation not starting or ending on a slot boundary
+create type rsvp.slot_rounding as enum ('reject', 'outward', 'nearest');
+
+-- reservations of a resource start and end on slot boundaries, counted from midnight in the time zone of
+-- the resource, and last between min_duration and max_duration. Zero means any
+alter table rsvp.resources
+    add column slot         interval           not null default '0',
+    add column min_duration interval           not null default '0',
+    add column max_duration interval           not null default '0',
+    add column rounding     rsvp.slot_rounding not null default 'reject';
+
+-- the timespan snapped to the slots of the resource as its rounding says, raise RV004 if it's misaligned
+-- and the resource rejects it, or it doesn't last between min_duration and max_duration
+create or replace function rsvp.align_timespan(res rsvp.resources, timespan tstzrange) returns tstzrange as
+$$
+declare
+    tz         text;
+    local_from timestamp;
+    local_to   timestamp;
+    from_floor timestamp;
+    to_floor   timestamp;
+    length     interval;
+begin
+    if res.slot > '0' then
+        tz := coalesce(nullif(res.time_zone, ''), 'UTC');
+        local_from := lower(timespan) at time zone tz;
+        local_to := upper(timespan) at time zone tz;
+        from_floor := date_bin(res.slot, local_from, local_from::date);
+        to_floor := date_bin(res.slot, local_to, local_to::date);
+
+        if from_floor <> local_from or to_floor <> local_to then
+            if res.rounding = 'reject' then
+                raise exception 'misaligned reservation: %', res.id
+                    using errcode = 'RV004',
+                        detail = format('%s has to start and end on a %s boundary, got %s to %s',
+                                        res.id, res.slot, local_from, local_to);
+            elsif res.rounding = 'outward' then
+                local_from := from_floor;
+                local_to := case when to_floor = local_to then to_floor else to_floor + res.slot end;
+            else
+                local_from := case when local_from - from_floor < res.slot / 2 then from_floor else from_floor + res.slot end;
+                local_to := case when local_to - to_floor < res.slot / 2 then to_floor else to_floor + res.slot end;
+            end if;
+
+            if local_from >= local_to then
+                raise exception 'misaligned reservation: %', res.id
+                    using errcode = 'RV004',
+                        detail = format('%s has to be reserved for at least a %s slot', res.id, res.slot);
+            end if;
+            timespan := tstzrange(local_from at time zone tz, local_to at time zone tz);
+        end if;
+    end if;
+
+    length := upper(timespan) - lower(timespan);
+    if res.min_duration > '0' and length < res.min_duration then
+        raise exception 'misaligned reservation: %', res.id
+            using errcode = 'RV004',
+                detail = format('%s has to be reserved for at least %s, got %s', res.id, res.min_duration, length);
+    end if;
+    if res.max_duration > '0' and length > res.max_duration then
+        raise exception 'misaligned reservation: %', res.id
+            using errcode = 'RV004',
+                detail = format('%s could be reserved for at most %s, got %s', res.id, res.max_duration, length);
+    end if;
+
+    return timespan;
+end;
+$$ language plpgsql;
+
+-- triggers of the same event fire in alphabetical order, this one runs before the calendar and capacity
+-- checks so they see the snapped timespan
+create or replace function rsvp.reservation_align_check() returns trigger as
+$$
+declare
+    res rsvp.resources;
+begin
+    -- blocks close a resource on purpose, they could be anywhere
+    if NEW.status = 'blocked' or not rsvp.holds_window(NEW) then
+        return NEW;
+    end if;
+
+    -- unknown resources are reported by rsvp.reservation_capacity_check
+    select * into res from rsvp.resources where id = NEW.resource_id;
+    if found then
+        NEW.timespan := rsvp.align_timespan(res, NEW.timespan);
+    end if;
+
+    return NEW;
+end;
+$$ language plpgsql;
+
+create trigger reservation_align_check
+    before insert or update of resource_id, timespan
+    on rsvp.reservations
+    for each row
+execute procedure rsvp.reservation_align_check();
+
+-- entries wait for the window they would be promoted to, a misaligned one would never be
+create or replace function rsvp.waitlist_align_check() returns trigger as
+$$
+declare
+    res rsvp.resources;
+begin
+    select * into res from rsvp.resources where id = NEW.resource_id;
+    if found then
+        NEW.timespan := rsvp.align_timespan(res, NEW.timespan);
+    end if;
+
+    return NEW;
+end;
+$$ language plpgsql;
+
+create trigger waitlist_align_check
+    before insert or update of resource_id, timespan
+    on rsvp.waitlist
+    for each row
+execute procedure rsvp.waitlist_align_check();
@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use sqlx::types::Uuid;
+use sqlx::Row;
+
+use abi::{CancelBundleRequest, Error, Reservation, ReservationStatus, RsvpStatus};
+
+use crate::manager::{insert_reservation, string_to_option, InsertOptions};
+use crate::{BundleId, Bundles, ReservationManager};
+
+#[async_trait]
+impl Bundles for ReservationManager {
+    async fn reserve_bundle(&self, rsvps: Vec<Reservation>) -> Result<Vec<Reservation>, Error> {
+        if rsvps.is_empty() {
+            return Err(Error::EmptyBundle);
+        }
+        for rsvp in rsvps.iter() {
+            rsvp.validate()?;
+        }
+
+        let mut tx = self.begin().await?;
+        let id: Uuid = sqlx::query("SELECT gen_random_uuid()")
+            .fetch_one(&mut tx)
+            .await?
+            .get(0);
+
+        let mut inserted = Vec::with_capacity(rsvps.len());
+        let mut conflicts = vec![];
+        for rsvp in rsvps.iter() {
+            // a conflict only rolls back its own insert, keep going to report the other conflicts
+            match insert_reservation(&mut tx, rsvp, InsertOptions { bundle_id: Some(id), ..Default::default() }).await {
+                Ok(rsvp) => inserted.push(rsvp),
+                Err(Error::ConflictError(info)) => conflicts.push(info),
+                Err(e) => return Err(e),
+            }
+        }
+
+        if !conflicts.is_empty() {
+            tx.rollback().await?;
+            let mut filled = Vec::with_capacity(conflicts.len());
+            for info in conflicts {
+                filled.push(self.fill_conflict_id(info).await);
+            }
+            return Err(Error::BundleConflict(filled));
+        }
+        tx.commit().await?;
+
+        Ok(inserted)
+    }
+
+    async fn confirm_bundle(&self, id: BundleId) -> Result<Vec<Reservation>, Error> {
+        let id = parse_bundle_id(id)?;
+
+        let mut tx = self.begin().await?;
+        let rows = sqlx::query("SELECT status FROM rsvp.reservations WHERE bundle_id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_all(&mut tx)
+            .await?;
+        if rows.is_empty() {
+            return Err(Error::NotFound);
+        }
+        // reservations cancelled on their own are left alone, the others must be confirmable
+        for row in rows.iter() {
+            let status = ReservationStatus::from(row.get::<RsvpStatus, _>("status"));
+            if !status.is_final() {
+                status.transition(ReservationStatus::Confirmed)?;
+            }
+        }
+
+        // holds don't expire once they leave pending
+        let rsvps: Vec<Reservation> = sqlx::query_as(r#"WITH confirmed AS (
+            UPDATE rsvp.reservations SET status = 'confirmed', expires_at = NULL
+            WHERE bundle_id = $1 AND status = 'pending' RETURNING *
+        ) SELECT * FROM confirmed ORDER BY lower(timespan), id"#)
+            .bind(id)
+            .fetch_all(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(rsvps)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> Result<Vec<Reservation>, Error> {
+        let id = parse_bundle_id(request.bundle_id)?;
+
+        let mut tx = self.begin().await?;
+        let rows = sqlx::query("SELECT status FROM rsvp.reservations WHERE bundle_id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_all(&mut tx)
+            .await?;
+        // like confirming, cancelling a bundle twice is an invalid transition rather than a no-op
+        let statuses: Vec<ReservationStatus> = rows.iter()
+            .map(|row| ReservationStatus::from(row.get::<RsvpStatus, _>("status")))
+            .collect();
+        match statuses.iter().find(|status| !status.is_final()).or(statuses.first()) {
+            None => return Err(Error::NotFound),
+            Some(status) => status.transition(ReservationStatus::Cancelled)?,
+        };
+
+        let rsvps: Vec<Reservation> = sqlx::query_as(r#"WITH cancelled AS (
+            UPDATE rsvp.reservations
+            SET status = 'cancelled', expires_at = NULL, cancelled_at = now(), cancelled_by = $2, cancel_reason = $3
+            WHERE bundle_id = $1 AND status IN ('pending', 'confirmed') RETURNING *
+        ) SELECT * FROM cancelled ORDER BY lower(timespan), id"#)
+            .bind(id)
+            .bind(string_to_option(&request.cancelled_by))
+            .bind(string_to_option(&request.reason))
+            .fetch_all(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(rsvps)
+    }
+
+    async fn get_bundle(&self, id: BundleId) -> Result<Vec<Reservation>, Error> {
+        let id = parse_bundle_id(id)?;
+
+        let rsvps: Vec<Reservation> = sqlx::query_as(
+            "SELECT * FROM rsvp.reservations WHERE bundle_id = $1 ORDER BY lower(timespan), id")
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await?;
+        if rsvps.is_empty() {
+            return Err(Error::NotFound);
+        }
+
+        Ok(rsvps)
+    }
+}
+
+fn parse_bundle_id(id: BundleId) -> Result<Uuid, Error> {
+    Uuid::parse_str(&id).map_err(|_| Error::InvalidBundleId(id))
+}
+
+#[cfg(test)]
+mod test {
+    use abi::{ReservationConflictInfo, ReservationQueryBuilder, Resource};
+
+    use crate::{Resources, Rsvp};
+    use super::*;
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_bundle_should_link_every_reservation() {
+        let manager = setup(&migrated_pool).await;
+
+        let rsvps = manager.reserve_bundle(event_bundle()).await.unwrap();
+        assert_eq!(rsvps.len(), 3);
+        let bundle_id = rsvps[0].bundle_id.clone();
+        assert_ne!(bundle_id, "");
+        assert!(rsvps.iter().all(|r| r.bundle_id == bundle_id));
+        assert_eq!(manager.get_bundle(bundle_id.clone()).await.unwrap().len(), 3);
+
+        // one of them is cancelled on its own, the rest are confirmed together
+        manager.cancel(abi::CancelRequest { id: rsvps[2].id.clone(), version: rsvps[2].version, ..Default::default() })
+            .await
+            .unwrap();
+        let confirmed = manager.confirm_bundle(bundle_id.clone()).await.unwrap();
+        assert_eq!(confirmed.len(), 2);
+        assert!(confirmed.iter().all(|r| r.status == ReservationStatus::Confirmed as i32));
+        assert!(matches!(manager.confirm_bundle(bundle_id.clone()).await, Err(Error::InvalidTransition { .. })));
+
+        let request = CancelBundleRequest {
+            bundle_id: bundle_id.clone(),
+            cancelled_by: "gyg".to_string(),
+            reason: "event moved".to_string(),
+        };
+        let cancelled = manager.cancel_bundle(request).await.unwrap();
+        assert_eq!(cancelled.len(), 2);
+        assert!(cancelled.iter().all(|r| r.status == ReservationStatus::Cancelled as i32 && r.cancel_reason == "event moved"));
+
+        let request = CancelBundleRequest { bundle_id: bundle_id.clone(), ..Default::default() };
+        assert!(matches!(manager.cancel_bundle(request).await, Err(Error::InvalidTransition { .. })));
+
+        assert!(matches!(manager.get_bundle(Uuid::nil().to_string()).await, Err(Error::NotFound)));
+        assert!(matches!(manager.confirm_bundle(Uuid::nil().to_string()).await, Err(Error::NotFound)));
+        let request = CancelBundleRequest { bundle_id: Uuid::nil().to_string(), ..Default::default() };
+        assert!(matches!(manager.cancel_bundle(request).await, Err(Error::NotFound)));
+        assert!(matches!(manager.reserve_bundle(vec![]).await, Err(Error::EmptyBundle)));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_bundle_should_reserve_nothing_on_conflict() {
+        let manager = setup(&migrated_pool).await;
+        let taken = manager.reserve(Reservation::new_pending("other", "catering",
+                                                             "2023-01-03T11:30:00Z".parse().unwrap(),
+                                                             "2023-01-03T12:30:00Z".parse().unwrap(), ""))
+            .await
+            .unwrap();
+
+        let conflicts = match manager.reserve_bundle(event_bundle()).await {
+            Err(Error::BundleConflict(conflicts)) => conflicts,
+            ret => panic!("expect bundle conflict, got {:?}", ret),
+        };
+        assert_eq!(conflicts.len(), 1);
+        match &conflicts[0] {
+            ReservationConflictInfo::Parsed(conflict) => assert_eq!(conflict.old.id, Some(taken.id)),
+            info => panic!("expect parsed conflict, got {:?}", info),
+        }
+
+        // the room and the projector are still free
+        let query = ReservationQueryBuilder::default().user_id("gyg").build().unwrap();
+        assert!(manager.query(query).await.unwrap().is_empty());
+    }
+
+    async fn setup(pool: &sqlx::PgPool) -> ReservationManager {
+        let manager = ReservationManager::new(pool.clone());
+        for rid in ["room-1", "projector", "catering"] {
+            manager.create_resource(Resource::new(rid, "", "event", 1)).await.unwrap();
+        }
+        manager
+    }
+
+    /// a room with a projector from 10 to 12 and catering for the last hour
+    fn event_bundle() -> Vec<Reservation> {
+        vec![
+            Reservation::new_pending("gyg", "room-1", "2023-01-03T10:00:00Z".parse().unwrap(), "2023-01-03T12:00:00Z".parse().unwrap(), "kickoff"),
+            Reservation::new_pending("gyg", "projector", "2023-01-03T10:00:00Z".parse().unwrap(), "2023-01-03T12:00:00Z".parse().unwrap(), "kickoff"),
+            Reservation::new_pending("gyg", "catering", "2023-01-03T11:00:00Z".parse().unwrap(), "2023-01-03T12:00:00Z".parse().unwrap(), "kickoff"),
+        ]
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::{HashMap, VecDeque};
+
+use futures::stream;
+use sqlx::postgres::PgListener;
+use sqlx::types::Uuid;
+use sqlx::{PgPool, Row};
+
+use abi::{Error, ListenRequest, ListenResponse, Reservation, ReservationUpdateType, RsvpUpdateType};
+
+use crate::ListenStream;
+
+/// channel notified by rsvp.reservation_trigger on every change
+const CHANNEL: &str = "reservation_update";
+
+/// max number of changes loaded from rsvp.reservation_changes at once
+const BATCH_SIZE: i64 = 100;
+
+struct ChangeFeed {
+    pool: PgPool,
+    listener: PgListener,
+    consumer: Option<String>,
+    /// id of the last change handed out
+    offset: i64,
+    /// offset to persist for the consumer once the next change is requested
+    delivered: Option<i64>,
+    pending: VecDeque<ListenResponse>,
+}
+
+/// stream changes recorded in rsvp.reservation_changes after the starting offset,
+/// waiting for `notify reservation_update` once all recorded changes are sent
+pub(crate) async fn listen(pool: PgPool, request: ListenRequest) -> Result<ListenStream, Error> {
+    request.validate()?;
+
+    // subscribe before reading the offset, so no change is missed in between
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener.listen(CHANNEL).await?;
+
+    let consumer = request.get_consumer().map(|c| c.to_string());
+    let offset = match (request.last_change_id, &consumer) {
+        (Some(id), _) => id,
+        (None, Some(consumer)) => match consumer_offset(&pool, consumer).await? {
+            Some(id) => id,
+            None => last_change_id(&pool).await?,
+        },
+        (None, None) => last_change_id(&pool).await?,
+    };
+
+    if let Some(consumer) = &consumer {
+        save_offset(&pool, consumer, offset).await?;
+    }
+
+    let feed = ChangeFeed { pool, listener, consumer, offset, delivered: None, pending: VecDeque::new() };
+    let stream = stream::try_unfold(feed, |mut feed| async move {
+        // the previous change has been taken by the client, commit it
+        if let (Some(id), Some(consumer)) = (feed.delivered.take(), &feed.consumer) {
+            save_offset(&feed.pool, consumer, id).await?;
+        }
+
+        loop {
+            if let Some(change) = feed.pending.pop_front() {
+                feed.offset = change.change_id;
+                feed.delivered = Some(change.change_id);
+                return Ok(Some((change, feed)));
+            }
+
+            feed.pending.extend(load_changes(&feed.pool, feed.offset).await?);
+
+            if feed.pending.is_empty() {
+                feed.listener.recv().await?;
+            }
+        }
+    });
+
+    Ok(Box::pin(stream))
+}
+
+async fn last_change_id(pool: &PgPool) -> Result<i64, Error> {
+    let id: i64 = sqlx::query("SELECT coalesce(max(id), 0) FROM rsvp.reservation_changes")
+        .fetch_one(pool)
+        .await?
+        .get(0);
+
+    Ok(id)
+}
+
+async fn consumer_offset(pool: &PgPool, consumer: &str) -> Result<Option<i64>, Error> {
+    let row = sqlx::query("SELECT last_change_id FROM rsvp.reservation_consumers WHERE name = $1")
+        .bind(consumer)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+async fn save_offset(pool: &PgPool, consumer: &str, offset: i64) -> Result<(), Error> {
+    sqlx::query(r#"INSERT INTO rsvp.reservation_consumers (name, last_change_id) VALUES ($1, $2)
+    ON CONFLICT (name) DO UPDATE SET last_change_id = EXCLUDED.last_change_id, updated_at = now()"#)
+        .bind(consumer)
+        .bind(offset)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// load the changes made after offset, in the order they were made
+async fn load_changes(pool: &PgPool, offset: i64) -> Result<Vec<ListenResponse>, Error> {
+    let rows = sqlx::query(r#"SELECT id, reservation_id, op FROM rsvp.reservation_changes
+    WHERE id > $1 ORDER BY id LIMIT $2"#)
+        .bind(offset)
+        .bind(BATCH_SIZE)
+        .fetch_all(pool)
+        .await?;
+
+    let changes: Vec<(i64, Uuid, RsvpUpdateType)> = rows.iter()
+        .map(|row| (row.get("id"), row.get("reservation_id"), row.get("op")))
+        .collect();
+
+    let ids: Vec<Uuid> = changes.iter().map(|(_, rid, _)| *rid).collect();
+    let rsvps: Vec<Reservation> = sqlx::query_as("SELECT * FROM rsvp.reservations WHERE id = ANY($1)")
+        .bind(ids)
+        .fetch_all(pool)
+        .await?;
+    let rsvps: HashMap<String, Reservation> = rsvps.into_iter()
+        .map(|rsvp| (rsvp.id.clone(), rsvp))
+        .collect();
+
+    Ok(changes.into_iter().map(|(change_id, id, op)| {
+        let id = id.to_string();
+        // deleted reservations are gone, only id is populated
+        let reservation = rsvps.get(&id)
+            .cloned()
+            .unwrap_or(Reservation { id, ..Default::default() });
+
+        ListenResponse {
+            op: ReservationUpdateType::from(op) as i32,
+            reservation: Some(reservation),
+            change_id,
+        }
+    }).collect())
+}
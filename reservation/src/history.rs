@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use sqlx::types::Uuid;
+use sqlx::{FromRow, PgPool, Row};
+
+use abi::{Error, Reservation, ReservationHistoryEntry};
+
+/// load the history of a reservation recorded by rsvp.reservation_history_trigger, oldest first
+pub(crate) async fn load(pool: &PgPool, id: Uuid) -> Result<Vec<ReservationHistoryEntry>, Error> {
+    let mut entries: Vec<ReservationHistoryEntry> = sqlx::query_as(r#"SELECT id, reservation_id, op, actor, changed_at
+    FROM rsvp.reservation_history WHERE reservation_id = $1 ORDER BY id"#)
+        .bind(id)
+        .fetch_all(pool)
+        .await?;
+
+    // snapshots are kept as json, let postgres turn them back into rows of rsvp.reservations
+    let rows = sqlx::query(r#"SELECT h.id AS entry_id, false AS after, r.*
+    FROM rsvp.reservation_history h, jsonb_populate_record(NULL::rsvp.reservations, h.before) r
+    WHERE h.reservation_id = $1 AND h.before IS NOT NULL
+    UNION ALL
+    SELECT h.id AS entry_id, true AS after, r.*
+    FROM rsvp.reservation_history h, jsonb_populate_record(NULL::rsvp.reservations, h.after) r
+    WHERE h.reservation_id = $1 AND h.after IS NOT NULL"#)
+        .bind(id)
+        .fetch_all(pool)
+        .await?;
+
+    let mut snapshots: HashMap<(i64, bool), Reservation> = HashMap::new();
+    for row in rows.iter() {
+        snapshots.insert((row.get("entry_id"), row.get("after")), Reservation::from_row(row)?);
+    }
+
+    for entry in entries.iter_mut() {
+        entry.before = snapshots.remove(&(entry.id, false));
+        entry.after = snapshots.remove(&(entry.id, true));
+    }
+
+    Ok(entries)
+}
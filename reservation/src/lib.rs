@@ -1,34 +1,142 @@
+mod bundle;
+mod changes;
+mod history;
 mod manager;
+mod policy;
+mod resource;
+mod series;
+mod waitlist;
+
+use std::pin::Pin;
 
 use async_trait::async_trait;
+use chrono::Duration;
+use futures::Stream;
 use sqlx::PgPool;
 use abi::Error;
 
 pub type ReservationId = String;
 pub type UserId = String;
 pub type ResourceId = String;
+pub type SeriesId = String;
+pub type BundleId = String;
+pub type WaitlistEntryId = String;
+pub type ListenStream = Pin<Box<dyn Stream<Item = Result<abi::ListenResponse, Error>> + Send>>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReservationManager{
     pool: PgPool,
-
+    /// recorded in the history of the reservations changed by this manager
+    actor: Option<String>,
 }
 
 
 #[async_trait]
 pub trait Rsvp {
-    /// make a reservation
+    /// make a reservation, fail with PolicyViolation if it breaks the policy of its resource
     async fn reserve(&self, rsvp: abi::Reservation) -> Result<abi::Reservation, Error>;
-    /// change status (if current reservation pending change it to confirmed)
-    async fn change_status(&self, id: ReservationId) -> Result<abi::Reservation, Error>;
-    /// update note
-    async fn update_note(&self, id: ReservationId, note: String)
-                         -> Result<abi::Reservation, Error>;
-    /// delete reservation
-    async fn delete(&self, id: ReservationId) -> Result<(), Error>;
+    /// make a pending reservation, released unless its status is changed within ttl
+    async fn hold(&self, rsvp: abi::Reservation, ttl: Duration) -> Result<abi::Reservation, Error>;
+    /// make the reservation requested, a hold if request.hold is given. If the user already made
+    /// a reservation with the idempotency key of the request, that one is returned instead.
+    /// Fail with IdempotencyKeyReused if that reservation was asked for another resource, window or note
+    async fn make_reservation(&self, request: abi::ReserveRequest) -> Result<abi::Reservation, Error>;
+    /// reserve the first candidate resource free for the whole window. Fail with PoolConflict
+    /// listing the reservation blocking each candidate if none of them is
+    async fn reserve_any(&self, request: abi::ReserveAnyRequest) -> Result<abi::Reservation, Error>;
+    /// change status (if current reservation pending change it to confirmed),
+    /// fail with VersionMismatch if the reservation isn't at the version given in request
+    async fn change_status(&self, request: abi::ConfirmRequest) -> Result<abi::Reservation, Error>;
+    /// move reservation to status `to`, fail with InvalidTransition if `to` isn't reachable from its status
+    /// and with VersionMismatch if the reservation isn't at `version`
+    async fn transition(&self, id: ReservationId, to: abi::ReservationStatus, version: i64)
+                        -> Result<abi::Reservation, Error>;
+    /// block a resource, overlapping pending reservations are cancelled for the block.
+    /// Return the block and the cancelled reservations
+    async fn block(&self, request: abi::BlockRequest)
+                   -> Result<(abi::Reservation, Vec<abi::Reservation>), Error>;
+    /// lift a block (blocked -> released), return the lifted block
+    async fn unblock(&self, id: ReservationId) -> Result<abi::Reservation, Error>;
+    /// release the holds not confirmed in time, return the released reservations.
+    /// Waitlist entries for windows which have passed are dropped as well
+    async fn expire(&self) -> Result<Vec<abi::Reservation>, Error>;
+    /// update note, fail with VersionMismatch if the reservation isn't at the version given in request
+    async fn update_note(&self, request: abi::UpdateRequest) -> Result<abi::Reservation, Error>;
+    /// cancel reservation, it's kept with status cancelled and who cancelled it and why.
+    /// Fail with VersionMismatch if the reservation isn't at the version given in request
+    async fn cancel(&self, request: abi::CancelRequest) -> Result<abi::Reservation, Error>;
+    /// move reservation to another window and/or resource in one go, so the slot isn't lost in between.
+    /// Fail with ConflictError if the new window is taken, the reservation is left untouched then
+    async fn reschedule(&self, request: abi::RescheduleRequest) -> Result<abi::Reservation, Error>;
     /// get reservation by id
     async fn get(&self, id: ReservationId) -> Result<abi::Reservation, Error>;
     /// query reservations
     async fn query(&self, query: abi::ReservationQuery)
                    -> Result<Vec<abi::Reservation>, Error>;
-}
\ No newline at end of file
+    /// filter reservations page by page, return the pager of the page and its reservations
+    async fn filter(&self, filter: abi::ReservationFilter)
+                    -> Result<(abi::FilterPager, Vec<abi::Reservation>), Error>;
+    /// listen to reservation changes in the order they were made, resuming from
+    /// the last change id or the offset of the consumer given in request
+    async fn listen(&self, request: abi::ListenRequest) -> Result<ListenStream, Error>;
+    /// find the intervals the resources are free within the requested window, ordered by start time
+    async fn availability(&self, request: abi::AvailabilityRequest) -> Result<Vec<abi::FreeSlot>, Error>;
+    /// get the changes made to a reservation, oldest first
+    async fn history(&self, id: ReservationId) -> Result<Vec<abi::ReservationHistoryEntry>, Error>;
+}
+
+#[async_trait]
+pub trait Resources {
+    /// register a resource, new resources are always active
+    async fn create_resource(&self, resource: abi::Resource) -> Result<abi::Resource, Error>;
+    /// update name, type, capacity and active flag of a resource
+    async fn update_resource(&self, resource: abi::Resource) -> Result<abi::Resource, Error>;
+    /// get resource by id
+    async fn get_resource(&self, id: ResourceId) -> Result<abi::Resource, Error>;
+}
+
+#[async_trait]
+pub trait Policies {
+    /// replace the policy of a resource, or the policy for every resource if its resource id is empty
+    async fn set_policy(&self, policy: abi::Policy) -> Result<abi::Policy, Error>;
+    /// get the policy of a resource, or the policy for every resource if id is empty
+    async fn get_policy(&self, id: ResourceId) -> Result<abi::Policy, Error>;
+}
+
+#[async_trait]
+pub trait Series {
+    /// reserve all occurrences of a series, either all of them are reserved or none
+    async fn reserve_series(&self, series: abi::ReservationSeries)
+                            -> Result<(abi::ReservationSeries, Vec<abi::Reservation>), Error>;
+    /// replace the occurrences starting at or after `from` with the occurrences of a new series,
+    /// return the new series and its occurrences
+    async fn update_series(&self, request: abi::UpdateSeriesRequest)
+                           -> Result<(abi::ReservationSeries, Vec<abi::Reservation>), Error>;
+    /// cancel the occurrences starting at or after `from` (all of them if empty), return the cancelled ones
+    async fn cancel_series(&self, request: abi::CancelSeriesRequest) -> Result<Vec<abi::Reservation>, Error>;
+    /// get series by id
+    async fn get_series(&self, id: SeriesId) -> Result<abi::ReservationSeries, Error>;
+}
+
+#[async_trait]
+pub trait Bundles {
+    /// make all reservations of a bundle under a shared bundle id, either all of them are made or none
+    async fn reserve_bundle(&self, rsvps: Vec<abi::Reservation>) -> Result<Vec<abi::Reservation>, Error>;
+    /// confirm all reservations of a bundle, fail if any of them can't be confirmed
+    async fn confirm_bundle(&self, id: BundleId) -> Result<Vec<abi::Reservation>, Error>;
+    /// cancel the pending and confirmed reservations of a bundle, return the cancelled ones
+    async fn cancel_bundle(&self, request: abi::CancelBundleRequest) -> Result<Vec<abi::Reservation>, Error>;
+    /// get the reservations of a bundle
+    async fn get_bundle(&self, id: BundleId) -> Result<Vec<abi::Reservation>, Error>;
+}
+
+#[async_trait]
+pub trait Waitlist {
+    /// wait for a window of a resource. The entry is promoted to a pending hold once the window
+    /// is freed, right away if it's free already. Entries joined earlier are promoted first,
+    /// entries breaking a policy or a rule of the resource keep waiting. The window is snapped to the slots
+    /// of the resource, fail with InvalidSlot or OutsideOpeningHours if it could never be reserved
+    async fn join_waitlist(&self, entry: abi::WaitlistEntry) -> Result<abi::WaitlistEntry, Error>;
+    /// remove an entry still waiting, return the removed entry
+    async fn leave_waitlist(&self, id: WaitlistEntryId) -> Result<abi::WaitlistEntry, Error>;
+}
@@ -1,90 +1,618 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use sqlx::{PgPool, Row};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Connection, Executor, FromRow, PgPool, Postgres, Row, Transaction};
+use sqlx::postgres::PgPoolOptions;
 use sqlx::postgres::types::PgRange;
 use sqlx::types::Uuid;
 
-use abi::{Error, Reservation, ReservationQuery, ReservationStatus};
+use abi::{AvailabilityRequest, BlockRequest, CancelRequest, ConfirmRequest, DbConfig, Error, FilterPager, FreeSlot, ListenRequest, Reservation, ReservationConflictInfo, ReservationFilter, ReservationHistoryEntry, ReservationQuery, RescheduleRequest, ReservationStatus, ReserveAnyRequest, ReserveRequest, RsvpStatus, UpdateRequest};
 
-use crate::{ReservationId, ReservationManager, Rsvp};
+use crate::{changes, history, ListenStream, ReservationId, ReservationManager, Rsvp};
 
 #[async_trait]
 impl Rsvp for ReservationManager {
-    async fn reserve(&self, mut rsvp: Reservation) -> Result<Reservation, Error> {
-        // 参数校验
+    async fn reserve(&self, rsvp: Reservation) -> Result<Reservation, Error> {
+        self.insert_rsvp(rsvp, None, None).await
+    }
+
+    async fn hold(&self, mut rsvp: Reservation, ttl: Duration) -> Result<Reservation, Error> {
+        if ttl <= Duration::zero() {
+            return Err(Error::InvalidHoldDuration(ttl.num_seconds()));
+        }
+        // only pending reservations could be released
+        rsvp.status = ReservationStatus::Pending as i32;
+
+        self.insert_rsvp(rsvp, Some(ttl), None).await
+    }
+
+    async fn make_reservation(&self, request: ReserveRequest) -> Result<Reservation, Error> {
+        let key = request.get_idempotency_key()?;
+        let ttl = request.get_hold()?;
+        let mut rsvp = request.reservation.clone().unwrap_or_default();
+        if ttl.is_some() {
+            rsvp.status = ReservationStatus::Pending as i32;
+        }
+
+        self.insert_rsvp(rsvp, ttl, key).await
+    }
+
+    async fn block(&self, request: BlockRequest) -> Result<(Reservation, Vec<Reservation>), Error> {
+        let block = request.to_reservation()?;
+        let timespan: PgRange<DateTime<Utc>> = block.get_timespan()?.into();
+
+        let mut tx = self.begin().await?;
+        // blocks take precedence over pending reservations
+        let released: Vec<Reservation> = sqlx::query_as(r#"WITH released AS (
+            UPDATE rsvp.reservations
+            SET status = 'cancelled', expires_at = NULL, cancelled_at = now(), cancelled_by = $3, cancel_reason = $4
+            WHERE resource_id = $1 AND timespan && $2 AND status = 'pending' RETURNING *
+        ) SELECT * FROM released ORDER BY lower(timespan), id"#)
+            .bind(&block.resource_id)
+            .bind(timespan.clone())
+            .bind(string_to_option(&block.user_id))
+            .bind(format!("blocked: {}", block.note))
+            .fetch_all(&mut tx)
+            .await?;
+
+        let block: Reservation = match sqlx::query_as(r#"INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status)
+         VALUES ($1, $2, $3, $4, 'blocked') RETURNING *"#)
+            .bind(&block.user_id)
+            .bind(&block.resource_id)
+            .bind(timespan)
+            .bind(&block.note)
+            .fetch_one(&mut tx)
+            .await
+            .map_err(Error::from)
+        {
+            Ok(block) => block,
+            Err(Error::ConflictError(info)) => {
+                tx.rollback().await?;
+                return Err(Error::ConflictError(self.fill_conflict_id(info).await));
+            }
+            Err(e) => return Err(e),
+        };
+        tx.commit().await?;
+
+        Ok((block, released))
+    }
+
+    async fn unblock(&self, id: ReservationId) -> Result<Reservation, Error> {
+        self.transition_rsvp(id, ReservationStatus::Released, None).await
+    }
+
+    async fn expire(&self) -> Result<Vec<Reservation>, Error> {
+        let mut tx = self.begin().await?;
+        let rsvps: Vec<Reservation> = sqlx::query_as(r#"DELETE FROM rsvp.reservations
+        WHERE status = 'pending' AND expires_at <= now() RETURNING *"#)
+            .fetch_all(&mut tx)
+            .await?;
+        // nobody is waiting for a window which has passed
+        sqlx::query("DELETE FROM rsvp.waitlist WHERE reservation_id IS NULL AND upper(timespan) <= now()")
+            .execute(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(rsvps)
+    }
+
+    async fn reserve_any(&self, request: ReserveAnyRequest) -> Result<Reservation, Error> {
+        let mut rsvp = request.reservation.unwrap_or_default();
+        let candidates: Vec<String> = match (request.resource_ids.is_empty(), request.resource_type.is_empty()) {
+            (false, _) => request.resource_ids,
+            (true, false) => sqlx::query("SELECT id FROM rsvp.resources WHERE resource_type = $1 AND active ORDER BY id")
+                .bind(&request.resource_type)
+                .fetch_all(&self.pool)
+                .await?
+                .iter()
+                .map(|row| row.get(0))
+                .collect(),
+            (true, true) => return Err(Error::InvalidResourceId("".to_string())),
+        };
+
+        // the candidates only differ in the resource, the rest is checked once
+        if let Some(rid) = candidates.iter().find(|rid| rid.is_empty() || rid.len() > 64) {
+            return Err(Error::InvalidResourceId(rid.clone()));
+        }
+        rsvp.resource_id = candidates.first()
+            .cloned()
+            .ok_or_else(|| Error::UnknownResource(request.resource_type.clone()))?;
         rsvp.validate()?;
 
-        let timespan: PgRange<DateTime<Utc>> = rsvp.get_timespan()?.into();
+        let mut tx = self.begin().await?;
+        let mut conflicts = Vec::with_capacity(candidates.len());
+        for rid in candidates.iter() {
+            rsvp.resource_id = rid.clone();
+            // a conflict only rolls back its own insert, go on with the next candidate
+            match insert_reservation(&mut tx, &rsvp, InsertOptions::default()).await {
+                Ok(rsvp) => {
+                    tx.commit().await?;
+                    return Ok(rsvp);
+                }
+                Err(Error::ConflictError(info)) => conflicts.push(info),
+                Err(Error::InactiveResource(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        tx.rollback().await?;
+
+        // none of them could be reserved at all
+        if conflicts.is_empty() {
+            return Err(Error::InactiveResource(candidates.join(", ")));
+        }
+        let mut filled = Vec::with_capacity(conflicts.len());
+        for info in conflicts {
+            filled.push(self.fill_conflict_id(info).await);
+        }
+        Err(Error::PoolConflict(filled))
+    }
+
+    async fn change_status(&self, request: ConfirmRequest) -> Result<Reservation, Error> {
+        self.transition_rsvp(request.id, ReservationStatus::Confirmed, Some(request.version)).await
+    }
+
+    async fn transition(&self, id: ReservationId, to: ReservationStatus, version: i64) -> Result<Reservation, Error> {
+        self.transition_rsvp(id, to, Some(version)).await
+    }
+
+    async fn cancel(&self, request: CancelRequest) -> Result<Reservation, Error> {
+        let version = request.version;
+        self.cancel_rsvp(request, Some(version)).await
+    }
+
+    async fn update_note(&self, request: UpdateRequest) -> Result<Reservation, Error> {
+        let id = parse_id(request.id)?;
+
+        let mut tx = self.begin().await?;
+        lock_status(&mut tx, id, Some(request.version)).await?;
+
+        let rsvp: Reservation = sqlx::query_as(r#"UPDATE rsvp.reservations
+        SET note = $2
+        WHERE id = $1 RETURNING *"#)
+            .bind(id)
+            .bind(request.note)
+            .fetch_one(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(rsvp)
+    }
+
+    async fn reschedule(&self, request: RescheduleRequest) -> Result<Reservation, Error> {
+        let id = parse_id(request.id.clone())?;
+
+        let mut tx = self.begin().await?;
+        let status = lock_status(&mut tx, id, Some(request.version)).await?;
+        if status.is_final() {
+            return Err(Error::NotReschedulable(status));
+        }
 
-        let status = ReservationStatus::from_i32(rsvp.status)
-            .unwrap_or(ReservationStatus::Pending);
+        let current: Reservation = sqlx::query_as("SELECT * FROM rsvp.reservations WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut tx)
+            .await?;
+        let moved = request.apply(current)?;
+        let timespan: PgRange<DateTime<Utc>> = moved.get_timespan()?.into();
 
-        // execute sql
-        let id: Uuid = sqlx::query(r#"INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status)
-         VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status) RETURNING id"#)
-            .bind(rsvp.user_id.clone())
-            .bind(rsvp.resource_id.clone())
+        // the capacity check runs again for the new window, the old one is freed along the way
+        let rsvp: Reservation = match sqlx::query_as(r#"UPDATE rsvp.reservations
+        SET resource_id = $2, timespan = $3
+        WHERE id = $1 RETURNING *"#)
+            .bind(id)
+            .bind(&moved.resource_id)
             .bind(timespan)
-            .bind(rsvp.note.clone())
-            .bind(status.to_string())
+            .fetch_one(&mut tx)
+            .await
+            .map_err(Error::from)
+        {
+            Ok(rsvp) => rsvp,
+            Err(Error::ConflictError(info)) => {
+                tx.rollback().await?;
+                return Err(Error::ConflictError(self.fill_conflict_id(info).await));
+            }
+            Err(e) => return Err(e),
+        };
+        tx.commit().await?;
+
+        Ok(rsvp)
+    }
+
+    async fn get(&self, id: ReservationId) -> Result<Reservation, Error> {
+        let id = parse_id(id)?;
+
+        let rsvp: Reservation = sqlx::query_as("SELECT * FROM rsvp.reservations WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(rsvp)
+    }
+
+    async fn query(&self, query: ReservationQuery) -> Result<Vec<Reservation>, Error> {
+        query.validate()?;
+
+        let timespan = query.get_timespan()?;
+        let status = query.get_status().map(|s| s.to_string());
+
+        let rsvps: Vec<Reservation> = sqlx::query_as(
+            "SELECT * FROM rsvp.query($1, $2, $3, $4::rsvp.reservation_status, $5)")
+            .bind(string_to_option(&query.user_id))
+            .bind(string_to_option(&query.resource_id))
+            .bind(timespan)
+            .bind(status)
+            .bind(query.desc)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rsvps)
+    }
+
+    async fn filter(&self, filter: ReservationFilter) -> Result<(FilterPager, Vec<Reservation>), Error> {
+        filter.validate()?;
+
+        let page_size = filter.page_size;
+
+        // fetch one more reservation to know whether there is a next page
+        let mut rsvps = self.filter_page(&filter, filter.cursor, filter.desc, page_size + 1).await?;
+        let next = if rsvps.len() as i64 > page_size {
+            rsvps.pop();
+            rsvps.last().map(|(seq, _)| *seq)
+        } else {
+            None
+        };
+
+        // walk backwards from the cursor (inclusive) to find where the previous page starts
+        let prev = match filter.cursor {
+            Some(cursor) => {
+                let cursor = if filter.desc { cursor.saturating_sub(1) } else { cursor.saturating_add(1) };
+                let before = self.filter_page(&filter, Some(cursor), !filter.desc, page_size + 1).await?;
+                match before.len() as i64 {
+                    0 => None,
+                    n if n > page_size => Some(before[page_size as usize].0),
+                    _ => Some(filter.first_cursor()),
+                }
+            }
+            None => None,
+        };
+
+        let total: i64 = sqlx::query(r#"SELECT count(*) FROM rsvp.reservations
+        WHERE ($1::text IS NULL OR user_id = $1)
+        AND ($2::text IS NULL OR resource_id = $2)
+        AND ($3::rsvp.reservation_status IS NULL OR status = $3::rsvp.reservation_status)"#)
+            .bind(string_to_option(&filter.user_id))
+            .bind(string_to_option(&filter.resource_id))
+            .bind(filter.get_status().map(|s| s.to_string()))
             .fetch_one(&self.pool)
             .await?
             .get(0);
 
-        rsvp.id = id.to_string();
+        let pager = FilterPager { prev, next, total: Some(total) };
+
+        Ok((pager, rsvps.into_iter().map(|(_, rsvp)| rsvp).collect()))
+    }
+
+    async fn listen(&self, request: ListenRequest) -> Result<ListenStream, Error> {
+        changes::listen(self.pool.clone(), request).await
+    }
+
+    async fn availability(&self, request: AvailabilityRequest) -> Result<Vec<FreeSlot>, Error> {
+        request.validate()?;
+
+        let timespan: PgRange<DateTime<Utc>> = request.get_timespan()?.into();
+
+        let slots: Vec<FreeSlot> = sqlx::query_as(r#"SELECT rid AS resource_id, slot
+        FROM unnest($1::text[]) rid, rsvp.availability(rid, $2, $3, $4) slot
+        ORDER BY lower(slot), rid"#)
+            .bind(&request.resource_ids)
+            .bind(timespan)
+            .bind(request.get_min_duration()?)
+            .bind(request.include_pending)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(slots)
+    }
+
+    async fn history(&self, id: ReservationId) -> Result<Vec<ReservationHistoryEntry>, Error> {
+        let id = parse_id(id)?;
+
+        let entries = history::load(&self.pool, id).await?;
+        // reservations made before history was kept have none, but they still exist
+        if entries.is_empty() {
+            self.get(id.to_string()).await?;
+        }
+
+        Ok(entries)
+    }
+}
+
+impl ReservationManager {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, actor: None }
+    }
+
+    /// a manager recording `actor` as the one who made the changes, at most 64 characters are kept in the history
+    pub fn with_actor(&self, actor: impl Into<String>) -> Result<Self, Error> {
+        let actor = actor.into();
+        if actor.chars().count() > 64 {
+            return Err(Error::InvalidActor(actor));
+        }
+
+        Ok(Self { pool: self.pool.clone(), actor: Some(actor) })
+    }
+
+    pub async fn from_config(config: &DbConfig) -> Result<Self, Error> {
+        let pool = PgPoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect(&config.url)
+            .await?;
+
+        Ok(Self::new(pool))
+    }
+
+    /// begin a transaction, changes made in it are recorded as made by the actor
+    pub(crate) async fn begin(&self) -> Result<Transaction<'static, Postgres>, Error> {
+        let mut tx = self.pool.begin().await?;
+        if let Some(actor) = &self.actor {
+            sqlx::query("SELECT set_config('rsvp.actor', $1, true)")
+                .bind(actor)
+                .execute(&mut tx)
+                .await?;
+        }
+
+        Ok(tx)
+    }
+
+    /// move the reservation to status `to`, checking its version if given
+    async fn transition_rsvp(&self, id: ReservationId, to: ReservationStatus, version: Option<i64>)
+                             -> Result<Reservation, Error> {
+        if to == ReservationStatus::Cancelled {
+            return self.cancel_rsvp(CancelRequest { id, ..Default::default() }, version).await;
+        }
+        let id = parse_id(id)?;
+
+        let mut tx = self.begin().await?;
+        lock_status(&mut tx, id, version).await?.transition(to)?;
+
+        // holds don't expire once they leave pending
+        let rsvp: Reservation = sqlx::query_as(r#"UPDATE rsvp.reservations
+        SET status = $2::rsvp.reservation_status, expires_at = NULL
+        WHERE id = $1 RETURNING *"#)
+            .bind(id)
+            .bind(to.to_string())
+            .fetch_one(&mut tx)
+            .await?;
+        tx.commit().await?;
 
         Ok(rsvp)
     }
 
-    async fn change_status(&self, id: ReservationId) -> Result<Reservation, Error> {
-        let id = Uuid::parse_str(&id)
-            .map_err(|_| abi::Error::InvalidResourceId(id))?;
+    /// soft cancel the reservation, checking its version if given
+    async fn cancel_rsvp(&self, request: CancelRequest, version: Option<i64>) -> Result<Reservation, Error> {
+        let id = parse_id(request.id)?;
 
-        let rsvp: Reservation = sqlx::query_as(r#"UPDATe rsvp.reservations
-        SET status = 'confirmed'
-        where id = $1
-        AND status = 'pending' RETURNING *"#)
+        let mut tx = self.begin().await?;
+        lock_status(&mut tx, id, version).await?.transition(ReservationStatus::Cancelled)?;
+
+        let rsvp: Reservation = sqlx::query_as(r#"UPDATE rsvp.reservations
+        SET status = 'cancelled', expires_at = NULL, cancelled_at = now(), cancelled_by = $2, cancel_reason = $3
+        WHERE id = $1 RETURNING *"#)
             .bind(id)
-            .fetch_one(&self.pool)
+            .bind(string_to_option(&request.cancelled_by))
+            .bind(string_to_option(&request.reason))
+            .fetch_one(&mut tx)
             .await?;
+        tx.commit().await?;
+
+        Ok(rsvp)
+    }
+
+    /// insert the reservation, it's released ttl after now if ttl is given.
+    /// If key is given and the user already made a reservation with it, that one is returned instead
+    async fn insert_rsvp(&self, rsvp: Reservation, ttl: Option<Duration>, key: Option<&str>) -> Result<Reservation, Error> {
+        // 参数校验
+        rsvp.validate()?;
+
+        let window = rsvp.get_timespan()?;
+        // what a retry with the same key has to ask for again
+        let request = format!("{}\n{}\n{}\n{}", rsvp.resource_id, window.start.timestamp_micros(),
+                              window.end.timestamp_micros(), rsvp.note);
+
+        let mut tx = self.begin().await?;
+        if let Some(key) = key {
+            // a hold made with the key that has expired is gone, the retry makes a new one
+            sqlx::query(r#"DELETE FROM rsvp.reservations
+            WHERE user_id = $1 AND idempotency_key = $2 AND status = 'pending' AND expires_at <= now()"#)
+                .bind(&rsvp.user_id)
+                .bind(key)
+                .execute(&mut tx)
+                .await?;
+
+            if let Some(existing) = find_by_key(&mut tx, &rsvp.user_id, key, &request).await? {
+                return Ok(existing);
+            }
+        }
+
+        let options = InsertOptions { ttl, key: key.map(|key| (key, request.as_str())), ..Default::default() };
+        let rsvp = match insert_reservation(&mut tx, &rsvp, options).await {
+            Ok(rsvp) => rsvp,
+            Err(Error::ConflictError(info)) => {
+                tx.rollback().await?;
+                // the window may be taken by a concurrent request with the same key
+                if let Some(key) = key {
+                    if let Some(existing) = find_by_key(&self.pool, &rsvp.user_id, key, &request).await? {
+                        return Ok(existing);
+                    }
+                }
+                return Err(Error::ConflictError(self.fill_conflict_id(info).await));
+            }
+            Err(e) => return Err(e),
+        };
+        tx.commit().await?;
 
         Ok(rsvp)
     }
 
-    async fn update_note(&self, _id: ReservationId, _note: String) -> Result<Reservation, Error> {
-        todo!()
+    /// postgres only reports the conflicting window, look up the reservation holding it
+    pub(crate) async fn fill_conflict_id(&self, mut info: ReservationConflictInfo) -> ReservationConflictInfo {
+        if let ReservationConflictInfo::Parsed(conflict) = &mut info {
+            let timespan: PgRange<DateTime<Utc>> = (conflict.old.start..conflict.old.end).into();
+            let id: Option<Uuid> = sqlx::query("SELECT id FROM rsvp.reservations WHERE resource_id = $1 AND timespan = $2")
+                .bind(&conflict.old.rid)
+                .bind(timespan)
+                .fetch_optional(&self.pool)
+                .await
+                .ok()
+                .flatten()
+                .map(|row| row.get(0));
+
+            conflict.old.id = id.map(|id| id.to_string());
+        }
+
+        info
     }
 
-    async fn delete(&self, _id: ReservationId) -> Result<(), Error> {
-        todo!()
+    /// fetch a page of reservations after cursor in the given direction, along with their seq
+    async fn filter_page(&self, filter: &ReservationFilter, cursor: Option<i64>, desc: bool, page_size: i64)
+                         -> Result<Vec<(i64, Reservation)>, Error> {
+        let rows = sqlx::query("SELECT * FROM rsvp.filter($1, $2, $3::rsvp.reservation_status, $4, $5, $6)")
+            .bind(string_to_option(&filter.user_id))
+            .bind(string_to_option(&filter.resource_id))
+            .bind(filter.get_status().map(|s| s.to_string()))
+            .bind(cursor)
+            .bind(desc)
+            .bind(page_size)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| Ok((row.get("seq"), Reservation::from_row(row)?)))
+            .collect()
     }
+}
 
-    async fn get(&self, _id: ReservationId) -> Result<Reservation, Error> {
-        todo!()
+/// lock the reservation until the transaction ends, return its current status.
+/// Fail with VersionMismatch if version is given and the reservation has moved on since
+async fn lock_status(tx: &mut Transaction<'_, Postgres>, id: Uuid, version: Option<i64>) -> Result<ReservationStatus, Error> {
+    let row = sqlx::query("SELECT status, version, expires_at <= now() AS expired FROM rsvp.reservations WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+    let status: RsvpStatus = row.get("status");
+    let expired: Option<bool> = row.get("expired");
+
+    // an expired hold is as good as released, even if the reaper hasn't got to it yet
+    if expired == Some(true) {
+        return Err(Error::NotFound);
     }
 
-    async fn query(&self, _query: ReservationQuery) -> Result<Vec<Reservation>, Error> {
-        todo!()
+    let actual: i64 = row.get("version");
+    match version {
+        // versions start from 1, so the client didn't send one
+        Some(expected) if expected < 1 => Err(Error::InvalidVersion(expected)),
+        Some(expected) if expected != actual => Err(Error::VersionMismatch { expected, actual }),
+        _ => Ok(status.into()),
     }
 }
 
-impl ReservationManager {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+/// what a reservation is inserted with besides its own fields
+#[derive(Default)]
+pub(crate) struct InsertOptions<'a> {
+    /// the reservation is a hold, released unless confirmed within ttl
+    pub ttl: Option<Duration>,
+    /// idempotency key and the request made with it
+    pub key: Option<(&'a str, &'a str)>,
+    pub series_id: Option<Uuid>,
+    pub bundle_id: Option<Uuid>,
+}
+
+/// insert the reservation in a savepoint of tx, so tx could go on if the insert fails, e.g. with a conflict.
+/// If a concurrent request with the same idempotency key got there first, its reservation is returned
+pub(crate) async fn insert_reservation(tx: &mut Transaction<'_, Postgres>, rsvp: &Reservation, options: InsertOptions<'_>)
+                                       -> Result<Reservation, Error> {
+    let timespan: PgRange<DateTime<Utc>> = rsvp.get_timespan()?.into();
+    let status = ReservationStatus::from_i32(rsvp.status)
+        .unwrap_or(ReservationStatus::Pending);
+    let (key, request) = options.key.unzip();
+
+    let mut savepoint = tx.begin().await?;
+    let inserted: Result<Option<Reservation>, Error> = sqlx::query_as(r#"INSERT INTO rsvp.reservations
+    (user_id, resource_id, timespan, note, status, expires_at, idempotency_key, idempotency_request, series_id, bundle_id)
+    VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status, now() + $6::interval, $7, md5($8), $9, $10)
+    ON CONFLICT (user_id, idempotency_key) DO NOTHING RETURNING *"#)
+        .bind(&rsvp.user_id)
+        .bind(&rsvp.resource_id)
+        .bind(timespan)
+        .bind(&rsvp.note)
+        .bind(status.to_string())
+        .bind(options.ttl)
+        .bind(key)
+        .bind(request)
+        .bind(options.series_id)
+        .bind(options.bundle_id)
+        .fetch_optional(&mut *savepoint)
+        .await
+        .map_err(Error::from);
+
+    let ret = match inserted {
+        Ok(Some(rsvp)) => Ok(rsvp),
+        // a concurrent request with the same key got there first
+        Ok(None) => find_by_key(&mut *savepoint, &rsvp.user_id, key.unwrap_or_default(), request.unwrap_or_default())
+            .await
+            .and_then(|existing| existing.ok_or(Error::NotFound)),
+        Err(e) => Err(e),
+    };
+    match ret {
+        Ok(_) => savepoint.commit().await?,
+        Err(_) => savepoint.rollback().await?,
+    }
+
+    ret
+}
+
+/// the reservation the user made with the idempotency key,
+/// fail with IdempotencyKeyReused if it was made for another request
+async fn find_by_key<'e>(executor: impl Executor<'e, Database = Postgres>, user_id: &str, key: &str, request: &str)
+                         -> Result<Option<Reservation>, Error> {
+    let row = sqlx::query(r#"SELECT *, idempotency_request = md5($3) AS same_request
+    FROM rsvp.reservations WHERE user_id = $1 AND idempotency_key = $2"#)
+        .bind(user_id)
+        .bind(key)
+        .bind(request)
+        .fetch_optional(executor)
+        .await?;
+
+    match row {
+        Some(row) if row.get::<Option<bool>, _>("same_request") == Some(false) => {
+            Err(Error::IdempotencyKeyReused(key.to_string()))
+        }
+        Some(row) => Ok(Some(Reservation::from_row(&row)?)),
+        None => Ok(None),
     }
 }
 
+fn parse_id(id: ReservationId) -> Result<Uuid, Error> {
+    Uuid::parse_str(&id).map_err(|_| Error::InvalidReservationId(id))
+}
+
+pub(crate) fn string_to_option(s: &str) -> Option<&str> {
+    if s.is_empty() { None } else { Some(s) }
+}
+
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
     use chrono::FixedOffset;
-    use abi::ReservationConflictInfo;
+    use futures::StreamExt;
+    use prost_types::Timestamp;
+    use abi::{Policy, ReservationFilterBuilder, ReservationQueryBuilder, ReservationUpdateType, Resource};
+    use crate::{Policies, Resources};
     use super::*;
 
     #[sqlx_database_tester::test(
     pool(variable = "migrated_pool", migrations = "../migrations")
     )]
     async fn reserve_should_work_for_valid_window() {
-        let manager = ReservationManager::new(migrated_pool.clone());
+        let manager = setup(&migrated_pool).await;
         let start: DateTime<FixedOffset> = "2022-12-25T15:00:00-0700".parse().unwrap();
         let end: DateTime<FixedOffset> = "2022-12-28T12:00:00-0700".parse().unwrap();
 
@@ -99,7 +627,7 @@ mod test {
     pool(variable = "migrated_pool", migrations = "../migrations")
     )]
     async fn reserve_should_error_with_conflict() {
-        let manager = ReservationManager::new(migrated_pool.clone());
+        let manager = setup(&migrated_pool).await;
         let rsvp_first = Reservation::new_pending("Geng",
                                                   "ocean-view-room-714",
                                                   "2022-12-25T15:00:00-0700".parse().unwrap(),
@@ -112,12 +640,13 @@ mod test {
                                                 "2022-12-28T12:00:00-0700".parse().unwrap(),
                                                 "error");
 
-        let _ = manager.reserve(rsvp_first).await.unwrap();
+        let first = manager.reserve(rsvp_first).await.unwrap();
 
         // should be error
         let error = manager.reserve(rsvp_sec).await.unwrap_err();
 
         if let Error::ConflictError(ReservationConflictInfo::Parsed(info)) = error {
+            assert_eq!(info.old.id, Some(first.id));
             assert_eq!(info.old.rid, "ocean-view-room-714");
             assert_eq!(info.old.start.to_rfc3339(), "2022-12-25T22:00:00+00:00");
             assert_eq!(info.old.end.to_rfc3339(), "2022-12-28T19:00:00+00:00");
@@ -126,11 +655,81 @@ mod test {
         }
     }
 
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_any_should_take_the_first_free_resource() {
+        let manager = setup(&migrated_pool).await;
+        for rid in ["desk-a", "desk-b", "desk-c"] {
+            manager.create_resource(Resource::new(rid, "", "desk", 1)).await.unwrap();
+        }
+        manager.update_resource(Resource { active: false, ..Resource::new("desk-a", "", "desk", 1) }).await.unwrap();
+        let taken = manager.reserve(new_rsvp("a", "desk-b", "2022-12-25T09:00:00Z", "2022-12-25T17:00:00Z"))
+            .await
+            .unwrap();
+
+        let request = ReserveAnyRequest {
+            resource_type: "desk".to_string(),
+            reservation: Some(new_rsvp("b", "", "2022-12-25T09:00:00Z", "2022-12-25T17:00:00Z")),
+            ..Default::default()
+        };
+        let rsvp = manager.reserve_any(request.clone()).await.unwrap();
+        assert_eq!(rsvp.resource_id, "desk-c");
+        assert_eq!(rsvp.user_id, "b");
+
+        // every candidate is taken now, the conflicts tell by what
+        let request = ReserveAnyRequest {
+            resource_ids: vec!["desk-c".to_string(), "desk-a".to_string(), "desk-b".to_string()],
+            ..request
+        };
+        match manager.reserve_any(request).await {
+            Err(Error::PoolConflict(conflicts)) => {
+                let ids: Vec<_> = conflicts.iter()
+                    .map(|info| match info {
+                        ReservationConflictInfo::Parsed(conflict) => conflict.old.id.clone(),
+                        ReservationConflictInfo::UnParsed(_) => None,
+                    })
+                    .collect();
+                assert_eq!(ids, vec![Some(rsvp.id), Some(taken.id)]);
+            }
+            ret => panic!("should conflict with every candidate, got {:?}", ret),
+        }
+
+        // no active resource of the type
+        let request = ReserveAnyRequest {
+            resource_type: "parking".to_string(),
+            reservation: Some(new_rsvp("b", "", "2022-12-25T09:00:00Z", "2022-12-25T17:00:00Z")),
+            ..Default::default()
+        };
+        assert!(matches!(manager.reserve_any(request.clone()).await, Err(Error::UnknownResource(_))));
+
+        // only inactive candidates
+        let request = ReserveAnyRequest {
+            resource_ids: vec!["desk-a".to_string()],
+            ..request
+        };
+        assert!(matches!(manager.reserve_any(request.clone()).await, Err(Error::InactiveResource(_))));
+
+        // the reservation is checked before any candidate is tried
+        let request = ReserveAnyRequest {
+            reservation: Some(new_rsvp("", "", "2022-12-25T09:00:00Z", "2022-12-25T17:00:00Z")),
+            ..request
+        };
+        assert!(matches!(manager.reserve_any(request.clone()).await, Err(Error::InvalidUserId(_))));
+
+        // no candidate given at all
+        let request = ReserveAnyRequest {
+            reservation: Some(new_rsvp("b", "", "2022-12-25T09:00:00Z", "2022-12-25T17:00:00Z")),
+            ..Default::default()
+        };
+        assert!(matches!(manager.reserve_any(request).await, Err(Error::InvalidResourceId(_))));
+    }
+
     #[sqlx_database_tester::test(
     pool(variable = "migrated_pool", migrations = "../migrations")
     )]
     async fn reserve_change_status_should_work() {
-        let manager = ReservationManager::new(migrated_pool.clone());
+        let manager = setup(&migrated_pool).await;
         let rsvp = Reservation::new_pending("Geng",
                                                   "ocean-view-room-714",
                                                   "2022-12-25T15:00:00-0700".parse().unwrap(),
@@ -138,7 +737,7 @@ mod test {
                                                   "ok");
         let rsvp = manager.reserve(rsvp).await.unwrap();
 
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
+        let rsvp = manager.change_status(confirm(&rsvp)).await.unwrap();
 
         assert_eq!(rsvp.status, ReservationStatus::Confirmed as i32)
     }
@@ -146,8 +745,8 @@ mod test {
     #[sqlx_database_tester::test(
     pool(variable = "migrated_pool", migrations = "../migrations")
     )]
-    async fn reserve_change_status_not_pending_should_do_nothing() {
-        let manager = ReservationManager::new(migrated_pool.clone());
+    async fn reserve_change_status_not_pending_should_fail() {
+        let manager = setup(&migrated_pool).await;
         let rsvp = Reservation::new_pending("Geng",
                                             "ocean-view-room-714",
                                             "2022-12-25T15:00:00-0700".parse().unwrap(),
@@ -155,9 +754,1048 @@ mod test {
                                             "ok");
         let rsvp = manager.reserve(rsvp).await.unwrap();
 
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
+        let rsvp = manager.change_status(confirm(&rsvp)).await.unwrap();
+
+        let ret = manager.change_status(confirm(&rsvp)).await.unwrap_err();
+        assert!(matches!(ret, Error::InvalidTransition { from: ReservationStatus::Confirmed, to: ReservationStatus::Confirmed }));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn transition_to_final_status_should_free_the_window() {
+        let manager = setup(&migrated_pool).await;
+        let rsvp = manager.reserve(new_rsvp("a", "room-0", "2022-12-25T09:00:00Z", "2022-12-25T13:00:00Z"))
+            .await
+            .unwrap();
+
+        let err = manager.transition(rsvp.id.clone(), ReservationStatus::Rejected, 0).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidVersion(0)));
+        let err = manager.transition(rsvp.id.clone(), ReservationStatus::Rejected, rsvp.version + 1).await.unwrap_err();
+        assert!(matches!(err, Error::VersionMismatch { .. }));
+
+        let rsvp = manager.transition(rsvp.id, ReservationStatus::Rejected, rsvp.version).await.unwrap();
+        assert_eq!(rsvp.status, ReservationStatus::Rejected as i32);
+
+        let err = manager.transition(rsvp.id.clone(), ReservationStatus::Confirmed, rsvp.version).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidTransition { from: ReservationStatus::Rejected, to: ReservationStatus::Confirmed }));
+
+        // the rejected reservation doesn't hold its window any more
+        let other = manager.reserve(new_rsvp("b", "room-0", "2022-12-25T10:00:00Z", "2022-12-25T11:00:00Z"))
+            .await
+            .unwrap();
+        let other = manager.change_status(confirm(&other)).await.unwrap();
+        let other = manager.transition(other.id, ReservationStatus::Cancelled, other.version).await.unwrap();
+        assert_eq!(other.status, ReservationStatus::Cancelled as i32);
+
+        let err = manager.transition("b1e3c9c4-1c7e-4b7a-9d3f-3f1e2c9d8a77".to_string(), ReservationStatus::Confirmed, 1)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::NotFound));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reschedule_should_move_the_reservation() {
+        let manager = setup(&migrated_pool).await;
+        let first = manager.reserve(new_rsvp("a", "room-0", "2022-12-25T09:00:00Z", "2022-12-25T10:00:00Z"))
+            .await
+            .unwrap();
+        let second = manager.reserve(new_rsvp("b", "room-0", "2022-12-25T10:00:00Z", "2022-12-25T11:00:00Z"))
+            .await
+            .unwrap();
+        let mut changes = manager.listen(ListenRequest::default()).await.unwrap();
+
+        let request = RescheduleRequest {
+            id: second.id.clone(),
+            start: Some("2022-12-25T11:00:00Z".parse().unwrap()),
+            end: Some("2022-12-25T12:00:00Z".parse().unwrap()),
+            version: second.version,
+            ..Default::default()
+        };
+        let moved = manager.reschedule(request.clone()).await.unwrap();
+        assert_eq!(moved.start, request.start);
+        assert_eq!(moved.end, request.end);
+        assert_eq!(moved.version, second.version + 1);
+
+        // the new window is taken, the reservation stays where it was
+        let request = RescheduleRequest {
+            start: Some("2022-12-25T09:30:00Z".parse().unwrap()),
+            version: moved.version,
+            ..request
+        };
+        match manager.reschedule(request.clone()).await {
+            Err(Error::ConflictError(ReservationConflictInfo::Parsed(info))) => assert_eq!(info.old.id, Some(first.id.clone())),
+            ret => panic!("should conflict with the first reservation, got {:?}", ret),
+        }
+        assert_eq!(manager.get(second.id.clone()).await.unwrap(), moved);
+
+        let request = RescheduleRequest { resource_id: "room-1".to_string(), ..request };
+        let moved = manager.reschedule(request).await.unwrap();
+        assert_eq!(moved.resource_id, "room-1");
+
+        let received = next_changes(&mut changes, 2).await;
+        assert_eq!(received, vec![
+            (ReservationUpdateType::Update as i32, second.id.clone()),
+            (ReservationUpdateType::Update as i32, second.id.clone()),
+        ]);
+
+        let cancelled = manager.cancel(CancelRequest { id: first.id.clone(), version: first.version, ..Default::default() })
+            .await
+            .unwrap();
+        let request = RescheduleRequest { id: first.id.clone(), version: cancelled.version, ..Default::default() };
+        assert!(matches!(manager.reschedule(request).await, Err(Error::NotReschedulable(ReservationStatus::Cancelled))));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn availability_should_return_free_intervals() {
+        let manager = setup(&migrated_pool).await;
+        manager.create_resource(Resource::new("desk-pool", "", "desk", 2)).await.unwrap();
+        let confirmed = manager.reserve(new_rsvp("a", "desk-pool", "2022-12-25T09:00:00Z", "2022-12-25T12:00:00Z"))
+            .await
+            .unwrap();
+        manager.change_status(confirm(&confirmed)).await.unwrap();
+        manager.reserve(new_rsvp("b", "desk-pool", "2022-12-25T10:00:00Z", "2022-12-25T11:00:00Z"))
+            .await
+            .unwrap();
+        manager.block(block_request("desk-pool", "2022-12-25T14:00:00Z", "2022-12-25T15:00:00Z")).await.unwrap();
+        manager.reserve(new_rsvp("c", "room-0", "2022-12-25T08:00:00Z", "2022-12-25T09:00:00Z"))
+            .await
+            .unwrap();
+
+        let mut request = AvailabilityRequest {
+            resource_ids: vec!["desk-pool".to_string(), "room-0".to_string()],
+            start: Some("2022-12-25T08:00:00Z".parse().unwrap()),
+            end: Some("2022-12-25T18:00:00Z".parse().unwrap()),
+            min_duration: None,
+            include_pending: true,
+        };
+        let slots = manager.availability(request.clone()).await.unwrap();
+        assert_eq!(slots, vec![
+            free_slot("desk-pool", "2022-12-25T08:00:00Z", "2022-12-25T10:00:00Z"),
+            free_slot("room-0", "2022-12-25T09:00:00Z", "2022-12-25T18:00:00Z"),
+            free_slot("desk-pool", "2022-12-25T11:00:00Z", "2022-12-25T14:00:00Z"),
+            free_slot("desk-pool", "2022-12-25T15:00:00Z", "2022-12-25T18:00:00Z"),
+        ]);
+
+        // pending reservations left out, only intervals of 4 hours or more
+        request.include_pending = false;
+        // nanos below a microsecond are dropped, as postgres can't keep them
+        request.min_duration = Some(prost_types::Duration { seconds: 4 * 3600, nanos: 1 });
+        let slots = manager.availability(request.clone()).await.unwrap();
+        assert_eq!(slots, vec![
+            free_slot("desk-pool", "2022-12-25T08:00:00Z", "2022-12-25T14:00:00Z"),
+            free_slot("room-0", "2022-12-25T08:00:00Z", "2022-12-25T18:00:00Z"),
+        ]);
+
+        let too_long = AvailabilityRequest { min_duration: Some(prost_types::Duration { seconds: i64::MAX, nanos: 0 }), ..request.clone() };
+        assert!(matches!(manager.availability(too_long).await, Err(Error::InvalidDuration(_))));
+
+        request.resource_ids.push("room-404".to_string());
+        assert!(matches!(manager.availability(request).await, Err(Error::UnknownResource(rid)) if rid == "room-404"));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_should_keep_buffers_of_resource_free() {
+        let manager = setup(&migrated_pool).await;
+        let room = Resource {
+            buffer_after: Some(prost_types::Duration { seconds: 2 * 3600, nanos: 0 }),
+            ..Resource::new("hotel-room-1", "", "hotel-room", 1)
+        };
+        manager.create_resource(room).await.unwrap();
+        let rsvp = manager.reserve(new_rsvp("a", "hotel-room-1", "2022-12-25T08:00:00Z", "2022-12-25T10:00:00Z"))
+            .await
+            .unwrap();
+        // customers still see the times they booked
+        assert_eq!(rsvp.start, Some("2022-12-25T08:00:00Z".parse().unwrap()));
+        assert_eq!(rsvp.end, Some("2022-12-25T10:00:00Z".parse().unwrap()));
+
+        // back to back leaves no time to clean the room
+        let err = manager.reserve(new_rsvp("b", "hotel-room-1", "2022-12-25T10:00:00Z", "2022-12-25T11:00:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ConflictError(_)));
+        let err = manager.reserve(new_rsvp("b", "hotel-room-1", "2022-12-25T06:00:00Z", "2022-12-25T07:00:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ConflictError(_)));
+
+        manager.reserve(new_rsvp("b", "hotel-room-1", "2022-12-25T12:00:00Z", "2022-12-25T13:00:00Z"))
+            .await
+            .unwrap();
+
+        let slots = manager.availability(AvailabilityRequest {
+            resource_ids: vec!["hotel-room-1".to_string()],
+            start: Some("2022-12-25T00:00:00Z".parse().unwrap()),
+            end: Some("2022-12-26T00:00:00Z".parse().unwrap()),
+            min_duration: None,
+            include_pending: true,
+        }).await.unwrap();
+        assert_eq!(slots, vec![
+            free_slot("hotel-room-1", "2022-12-25T00:00:00Z", "2022-12-25T06:00:00Z"),
+            free_slot("hotel-room-1", "2022-12-25T15:00:00Z", "2022-12-26T00:00:00Z"),
+        ]);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_should_keep_to_opening_hours() {
+        let manager = setup(&migrated_pool).await;
+        let weekday = |weekday: abi::Weekday| abi::OpeningHours {
+            weekday: weekday as i32,
+            opens_at: Some(prost_types::Duration { seconds: 8 * 3600, nanos: 0 }),
+            closes_at: Some(prost_types::Duration { seconds: 20 * 3600, nanos: 0 }),
+        };
+        let room = Resource {
+            time_zone: "Europe/Berlin".to_string(),
+            opening_hours: vec![weekday(abi::Weekday::Monday), weekday(abi::Weekday::Friday)],
+            holidays: vec!["2023-01-06".to_string()],
+            ..Resource::new("office-1", "", "meeting-room", 1)
+        };
+        manager.create_resource(room).await.unwrap();
+
+        // 08:00 to 10:00 in Berlin
+        manager.reserve(new_rsvp("a", "office-1", "2023-01-02T07:00:00Z", "2023-01-02T09:00:00Z")).await.unwrap();
+
+        let err = manager.reserve(new_rsvp("a", "office-1", "2023-01-02T18:30:00Z", "2023-01-02T19:30:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::OutsideOpeningHours(detail) if detail == "office-1 is open 08:00-20:00 on Monday (Europe/Berlin)"));
+        let err = manager.reserve(new_rsvp("a", "office-1", "2023-01-03T09:00:00Z", "2023-01-03T10:00:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::OutsideOpeningHours(detail) if detail == "office-1 is closed on Tuesday"));
+        let err = manager.reserve(new_rsvp("a", "office-1", "2023-01-06T09:00:00Z", "2023-01-06T10:00:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::OutsideOpeningHours(detail) if detail == "office-1 is closed on 2023-01-06"));
+
+        // blocks close the resource, they are fine any time
+        manager.block(block_request("office-1", "2023-01-07T00:00:00Z", "2023-01-08T00:00:00Z")).await.unwrap();
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_should_align_to_slots() {
+        let manager = setup(&migrated_pool).await;
+        let minutes = |m: i64| Some(prost_types::Duration { seconds: m * 60, nanos: 0 });
+        let strict = Resource {
+            slot: minutes(15),
+            min_duration: minutes(30),
+            max_duration: minutes(120),
+            ..Resource::new("court-1", "", "tennis-court", 1)
+        };
+        manager.create_resource(strict.clone()).await.unwrap();
+        manager.create_resource(Resource {
+            id: "court-2".to_string(),
+            rounding: abi::SlotRounding::Outward as i32,
+            ..strict.clone()
+        }).await.unwrap();
+        manager.create_resource(Resource {
+            id: "court-3".to_string(),
+            rounding: abi::SlotRounding::Nearest as i32,
+            ..strict
+        }).await.unwrap();
+
+        let err = manager.reserve(new_rsvp("a", "court-1", "2022-12-25T10:07:33.123Z", "2022-12-25T11:00:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidSlot(_)));
+        let err = manager.reserve(new_rsvp("a", "court-1", "2022-12-25T10:00:00Z", "2022-12-25T10:15:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidSlot(detail) if detail == "court-1 has to be reserved for at least 00:30:00, got 00:15:00"));
+        manager.reserve(new_rsvp("a", "court-1", "2022-12-25T10:00:00Z", "2022-12-25T11:00:00Z")).await.unwrap();
+
+        // snapped so the requested time is covered
+        let rsvp = manager.reserve(new_rsvp("a", "court-2", "2022-12-25T10:07:33.123Z", "2022-12-25T10:50:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(rsvp.start, Some("2022-12-25T10:00:00Z".parse().unwrap()));
+        assert_eq!(rsvp.end, Some("2022-12-25T11:00:00Z".parse().unwrap()));
+        let err = manager.reserve(new_rsvp("a", "court-2", "2022-12-25T12:00:00Z", "2022-12-25T15:00:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidSlot(_)));
+
+        // snapped to the closest boundaries, the reservation may get shorter or longer
+        let rsvp = manager.reserve(new_rsvp("a", "court-3", "2022-12-25T10:07:00Z", "2022-12-25T10:53:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(rsvp.start, Some("2022-12-25T10:00:00Z".parse().unwrap()));
+        assert_eq!(rsvp.end, Some("2022-12-25T11:00:00Z".parse().unwrap()));
+        let rsvp = manager.reserve(new_rsvp("a", "court-3", "2022-12-25T12:08:00Z", "2022-12-25T12:52:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(rsvp.start, Some("2022-12-25T12:15:00Z".parse().unwrap()));
+        assert_eq!(rsvp.end, Some("2022-12-25T12:45:00Z".parse().unwrap()));
+        // nothing is left once both ends snap to the same boundary
+        let err = manager.reserve(new_rsvp("a", "court-3", "2022-12-25T14:02:00Z", "2022-12-25T14:07:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidSlot(detail) if detail == "court-3 has to be reserved for at least a 00:15:00 slot"));
+
+        // policies see the snapped timespan, an hour asked for off the boundaries takes 75 minutes
+        manager.set_policy(Policy { resource_id: "court-2".to_string(), max_duration: minutes(60), ..Default::default() })
+            .await
+            .unwrap();
+        manager.reserve(new_rsvp("a", "court-2", "2022-12-25T12:10:00Z", "2022-12-25T13:00:00Z")).await.unwrap();
+        let err = manager.reserve(new_rsvp("a", "court-2", "2022-12-25T13:10:00Z", "2022-12-25T14:10:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::PolicyViolation { rule: abi::PolicyRule::MaxDuration, .. }));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn get_reservation_should_work() {
+        let manager = setup(&migrated_pool).await;
+        let rsvp = manager.reserve(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+
+        let ret = manager.get(rsvp.id.clone()).await.unwrap();
+
+        assert_eq!(ret, rsvp);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn get_reservation_with_invalid_id_should_fail() {
+        let manager = setup(&migrated_pool).await;
+
+        let err = manager.get("not-a-uuid".to_string()).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidReservationId(id) if id == "not-a-uuid"));
+
+        let err = manager.get(Uuid::nil().to_string()).await.unwrap_err();
+        assert!(matches!(err, Error::NotFound));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn update_note_should_work() {
+        let manager = setup(&migrated_pool).await;
+        let rsvp = manager.reserve(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+
+        let rsvp = manager.update_note(update_note(&rsvp, "late check-in")).await.unwrap();
+        assert_eq!(rsvp.note, "late check-in");
+
+        let rsvp = manager.get(rsvp.id).await.unwrap();
+        assert_eq!(rsvp.note, "late check-in");
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn retried_reserve_should_return_the_first_reservation() {
+        let manager = setup(&migrated_pool).await;
+        let request = ReserveRequest {
+            reservation: Some(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700")),
+            idempotency_key: "checkout-42".to_string(),
+            ..Default::default()
+        };
+        let first = manager.make_reservation(request.clone()).await.unwrap();
+
+        // without the key both retries would conflict with the first attempt
+        assert_eq!(manager.make_reservation(request.clone()).await.unwrap(), first);
+
+        // the key can't be reused for another window, resource or note
+        let shifted = ReserveRequest {
+            reservation: Some(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T16:00:00-0700", "2022-12-28T12:00:00-0700")),
+            ..request.clone()
+        };
+        assert!(matches!(manager.make_reservation(shifted).await, Err(Error::IdempotencyKeyReused(_))));
+        let mut renoted = request.clone();
+        renoted.reservation.as_mut().unwrap().note = "another note".to_string();
+        assert!(matches!(manager.make_reservation(renoted).await, Err(Error::IdempotencyKeyReused(_))));
+
+        // the key stays with the request even if the reservation changes later
+        let updated = manager.update_note(update_note(&first, "late check-in")).await.unwrap();
+        assert_eq!(manager.make_reservation(request.clone()).await.unwrap(), updated);
+
+        // keys are per user
+        let other = ReserveRequest {
+            reservation: Some(new_rsvp("yage", "room-0", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700")),
+            ..request
+        };
+        assert_ne!(manager.make_reservation(other).await.unwrap().id, first.id);
+
+        let long_key = ReserveRequest { idempotency_key: "k".repeat(65), ..Default::default() };
+        assert!(matches!(manager.make_reservation(long_key).await, Err(Error::InvalidIdempotencyKey(_))));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn retried_hold_should_replace_the_expired_one() {
+        let manager = setup(&migrated_pool).await;
+        let request = ReserveRequest {
+            reservation: Some(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700")),
+            hold: Some(prost_types::Duration { seconds: 0, nanos: 10_000_000 }),
+            idempotency_key: "checkout-42".to_string(),
+        };
+        let first = manager.make_reservation(request.clone()).await.unwrap();
+        assert!(first.expires_at.is_some());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let second = manager.make_reservation(request).await.unwrap();
+        assert_ne!(second.id, first.id);
+        assert!(matches!(manager.get(first.id).await, Err(Error::NotFound)));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn stale_version_should_be_rejected() {
+        let manager = setup(&migrated_pool).await;
+        let rsvp = manager.reserve(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+        assert_eq!(rsvp.version, 1);
+
+        // two agents start from the same version, the later one loses
+        let updated = manager.update_note(update_note(&rsvp, "late check-in")).await.unwrap();
+        assert_eq!(updated.version, 2);
+
+        let err = manager.update_note(update_note(&rsvp, "early check-in")).await.unwrap_err();
+        assert!(matches!(err, Error::VersionMismatch { expected: 1, actual: 2 }));
+        let err = manager.change_status(confirm(&rsvp)).await.unwrap_err();
+        assert!(matches!(err, Error::VersionMismatch { expected: 1, actual: 2 }));
+        let err = manager.cancel(CancelRequest { id: rsvp.id.clone(), version: rsvp.version, ..Default::default() })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::VersionMismatch { expected: 1, actual: 2 }));
+        assert_eq!(manager.get(rsvp.id.clone()).await.unwrap(), updated);
+
+        let confirmed = manager.change_status(confirm(&updated)).await.unwrap();
+        assert_eq!(confirmed.version, 3);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn history_should_record_every_change() {
+        let manager = setup(&migrated_pool).await;
+        let rsvp = manager.with_actor("Geng").unwrap()
+            .reserve(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+        let updated = manager.update_note(update_note(&rsvp, "late check-in")).await.unwrap();
+        let confirmed = manager.with_actor("front-desk").unwrap().change_status(confirm(&updated)).await.unwrap();
+
+        let history = manager.history(rsvp.id.clone()).await.unwrap();
+        let ops: Vec<_> = history.iter().map(|entry| (entry.op, entry.actor.as_str())).collect();
+        assert_eq!(ops, vec![
+            (ReservationUpdateType::Create as i32, "Geng"),
+            (ReservationUpdateType::Update as i32, ""),
+            (ReservationUpdateType::Update as i32, "front-desk"),
+        ]);
+        assert_eq!(history[0].before, None);
+        assert_eq!(history[0].after.as_ref(), Some(&rsvp));
+        assert_eq!(history[1].before.as_ref().unwrap().note, "ok");
+        assert_eq!(history[1].after.as_ref().unwrap().note, "late check-in");
+        assert_eq!(history[2].after.as_ref(), Some(&confirmed));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn history_should_outlive_expired_holds() {
+        let manager = setup(&migrated_pool).await;
+        let hold = manager.hold(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"),
+                                chrono::Duration::milliseconds(10))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        manager.with_actor("reaper").unwrap().expire().await.unwrap();
+
+        let history = manager.history(hold.id.clone()).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].op, ReservationUpdateType::Expire as i32);
+        assert_eq!(history[1].actor, "reaper");
+        assert_eq!(history[1].before.as_ref().unwrap().id, hold.id);
+        assert_eq!(history[1].after, None);
+
+        let err = manager.history(Uuid::nil().to_string()).await.unwrap_err();
+        assert!(matches!(err, Error::NotFound));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn cancel_reservation_should_keep_it() {
+        let manager = setup(&migrated_pool).await;
+        let rsvp = manager.reserve(new_rsvp("Geng", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+
+        let request = CancelRequest {
+            id: rsvp.id.clone(),
+            cancelled_by: "Geng".to_string(),
+            reason: "trip cancelled".to_string(),
+            version: rsvp.version,
+        };
+        let cancelled = manager.cancel(request.clone()).await.unwrap();
+        assert_eq!(cancelled.status, ReservationStatus::Cancelled as i32);
+        assert!(cancelled.cancelled_at.is_some());
+        assert_eq!(cancelled.cancelled_by, "Geng");
+        assert_eq!(cancelled.cancel_reason, "trip cancelled");
+        assert_eq!(manager.get(rsvp.id.clone()).await.unwrap(), cancelled);
+
+        // the window is free again, while the cancelled reservation is still queryable
+        manager.reserve(new_rsvp("yage", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+        let query = ReservationQueryBuilder::default()
+            .resource_id("ocean-view-room-714")
+            .status(ReservationStatus::Cancelled as i32)
+            .build()
+            .unwrap();
+        assert_eq!(manager.query(query).await.unwrap(), vec![cancelled.clone()]);
+
+        let err = manager.cancel(CancelRequest { version: cancelled.version, ..request }).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidTransition { from: ReservationStatus::Cancelled, to: ReservationStatus::Cancelled }));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn query_reservations_should_work() {
+        let manager = setup(&migrated_pool).await;
+        let first = manager.reserve(new_rsvp("Geng", "ocean-view-room-713", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+        let second = manager.reserve(new_rsvp("yage", "ocean-view-room-714", "2022-12-26T15:00:00-0700", "2022-12-29T12:00:00-0700"))
+            .await
+            .unwrap();
+        let third = manager.reserve(new_rsvp("Geng", "ocean-view-room-714", "2023-01-10T15:00:00-0700", "2023-01-12T12:00:00-0700"))
+            .await
+            .unwrap();
+        let third = manager.change_status(confirm(&third)).await.unwrap();
+
+        // no condition returns every reservation, ordered by start time
+        let query = ReservationQueryBuilder::default().build().unwrap();
+        let rsvps = manager.query(query).await.unwrap();
+        assert_eq!(rsvps, vec![first.clone(), second.clone(), third.clone()]);
+
+        // desc ordering
+        let query = ReservationQueryBuilder::default().desc(true).build().unwrap();
+        let rsvps = manager.query(query).await.unwrap();
+        assert_eq!(rsvps, vec![third.clone(), second.clone(), first.clone()]);
+
+        // resource id and user id
+        let query = ReservationQueryBuilder::default()
+            .resource_id("ocean-view-room-714")
+            .user_id("Geng")
+            .build()
+            .unwrap();
+        let rsvps = manager.query(query).await.unwrap();
+        assert_eq!(rsvps, vec![third.clone()]);
+
+        // status
+        let query = ReservationQueryBuilder::default()
+            .status(ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        let rsvps = manager.query(query).await.unwrap();
+        assert_eq!(rsvps, vec![first.clone(), second.clone()]);
+
+        // open-ended end time, overlapping reservations are included
+        let query = ReservationQueryBuilder::default()
+            .start("2022-12-28T00:00:00Z".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+        let rsvps = manager.query(query).await.unwrap();
+        assert_eq!(rsvps, vec![first.clone(), second.clone(), third.clone()]);
+
+        // open-ended start time
+        let query = ReservationQueryBuilder::default()
+            .end("2022-12-27T00:00:00Z".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+        let rsvps = manager.query(query).await.unwrap();
+        assert_eq!(rsvps, vec![first.clone(), second.clone()]);
+
+        // bounded window
+        let query = ReservationQueryBuilder::default()
+            .start("2022-12-30T00:00:00Z".parse::<Timestamp>().unwrap())
+            .end("2023-01-11T00:00:00Z".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+        let rsvps = manager.query(query).await.unwrap();
+        assert_eq!(rsvps, vec![third]);
+    }
+
+    #[test]
+    fn query_with_invalid_window_should_fail() {
+        let err = ReservationQueryBuilder::default()
+            .start("2023-01-11T00:00:00Z".parse::<Timestamp>().unwrap())
+            .end("2022-12-30T00:00:00Z".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(err, Error::InvalidTime));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn filter_should_page_forward_and_backward() {
+        let manager = setup(&migrated_pool).await;
+        let rsvps = make_rsvps(&manager, 12).await;
+
+        let filter = ReservationFilterBuilder::default().page_size(5).build().unwrap();
+        let (pager, page) = manager.filter(filter.clone()).await.unwrap();
+        assert_eq!(page, rsvps[0..5]);
+        assert_eq!(pager.prev, None);
+        assert_eq!(pager.total, Some(12));
+
+        let filter = ReservationFilter { cursor: pager.next, ..filter };
+        let (pager, page) = manager.filter(filter.clone()).await.unwrap();
+        assert_eq!(page, rsvps[5..10]);
+
+        let filter = ReservationFilter { cursor: pager.next, ..filter };
+        let (pager, page) = manager.filter(filter.clone()).await.unwrap();
+        assert_eq!(page, rsvps[10..12]);
+        assert_eq!(pager.next, None);
+
+        // go back to the first page
+        let filter = ReservationFilter { cursor: pager.prev, ..filter };
+        let (pager, page) = manager.filter(filter.clone()).await.unwrap();
+        assert_eq!(page, rsvps[5..10]);
+
+        let filter = ReservationFilter { cursor: pager.prev, ..filter };
+        let (pager, page) = manager.filter(filter).await.unwrap();
+        assert_eq!(page, rsvps[0..5]);
+        assert_eq!(pager.prev, None);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn filter_desc_should_page_forward_and_backward() {
+        let manager = setup(&migrated_pool).await;
+        let mut rsvps = make_rsvps(&manager, 7).await;
+        rsvps.reverse();
+
+        let filter = ReservationFilterBuilder::default().page_size(3).desc(true).build().unwrap();
+        let (pager, page) = manager.filter(filter.clone()).await.unwrap();
+        assert_eq!(page, rsvps[0..3]);
+        assert_eq!(pager.prev, None);
+
+        let filter = ReservationFilter { cursor: pager.next, ..filter };
+        let (pager, page) = manager.filter(filter.clone()).await.unwrap();
+        assert_eq!(page, rsvps[3..6]);
+
+        let filter = ReservationFilter { cursor: pager.next, ..filter };
+        let (pager, page) = manager.filter(filter.clone()).await.unwrap();
+        assert_eq!(page, rsvps[6..7]);
+        assert_eq!(pager.next, None);
+
+        let filter = ReservationFilter { cursor: pager.prev, ..filter };
+        let (pager, page) = manager.filter(filter.clone()).await.unwrap();
+        assert_eq!(page, rsvps[3..6]);
+
+        let filter = ReservationFilter { cursor: pager.prev, ..filter };
+        let (pager, page) = manager.filter(filter).await.unwrap();
+        assert_eq!(page, rsvps[0..3]);
+        assert_eq!(pager.prev, None);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn filter_should_honor_conditions() {
+        let manager = setup(&migrated_pool).await;
+        let rsvps = make_rsvps(&manager, 4).await;
+        let confirmed = manager.change_status(confirm(&rsvps[1])).await.unwrap();
+
+        let filter = ReservationFilterBuilder::default()
+            .status(ReservationStatus::Confirmed as i32)
+            .build()
+            .unwrap();
+        let (pager, page) = manager.filter(filter).await.unwrap();
+        assert_eq!(page, vec![confirmed]);
+        assert_eq!(pager.total, Some(1));
+        assert_eq!(pager.next, None);
+
+        let filter = ReservationFilterBuilder::default()
+            .resource_id("room-3")
+            .user_id("Geng")
+            .build()
+            .unwrap();
+        let (_, page) = manager.filter(filter).await.unwrap();
+        assert_eq!(page, rsvps[3..4]);
+    }
+
+    #[test]
+    fn filter_with_invalid_page_size_should_fail() {
+        let err = ReservationFilterBuilder::default().page_size(0).build().unwrap_err();
+        assert!(matches!(err, Error::InvalidPageSize(0)));
+
+        let err = ReservationFilterBuilder::default().page_size(101).build().unwrap_err();
+        assert!(matches!(err, Error::InvalidPageSize(101)));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn listen_should_stream_changes_in_order() {
+        let manager = setup(&migrated_pool).await;
+        let first = manager.reserve(new_rsvp("Geng", "ocean-view-room-713", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+
+        // without an offset, only changes made after listening are delivered
+        let mut changes = manager.listen(ListenRequest::default()).await.unwrap();
+
+        let second = manager.reserve(new_rsvp("yage", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700"))
+            .await
+            .unwrap();
+        let confirmed = manager.change_status(confirm(&second)).await.unwrap();
+        manager.cancel(CancelRequest { id: first.id.clone(), version: first.version, ..Default::default() }).await.unwrap();
+
+        let received = next_changes(&mut changes, 3).await;
+        assert_eq!(received, vec![
+            (ReservationUpdateType::Create as i32, second.id.clone()),
+            (ReservationUpdateType::Update as i32, confirmed.id),
+            (ReservationUpdateType::Update as i32, first.id),
+        ]);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn listen_should_replay_after_last_change_id() {
+        let manager = setup(&migrated_pool).await;
+        let rsvps = make_rsvps(&manager, 3).await;
+
+        let request = ListenRequest { last_change_id: Some(0), ..Default::default() };
+        let mut changes = manager.listen(request).await.unwrap();
+        let received = next_changes(&mut changes, 3).await;
+        assert_eq!(received, rsvps.iter()
+            .map(|rsvp| (ReservationUpdateType::Create as i32, rsvp.id.clone()))
+            .collect::<Vec<_>>());
+
+        // resume from the id of the first change, missed changes are replayed before live ones
+        let mut changes = manager.listen(ListenRequest { last_change_id: Some(1), ..Default::default() }).await.unwrap();
+        let confirmed = manager.change_status(confirm(&rsvps[0])).await.unwrap();
+        let received = next_changes(&mut changes, 3).await;
+        assert_eq!(received, vec![
+            (ReservationUpdateType::Create as i32, rsvps[1].id.clone()),
+            (ReservationUpdateType::Create as i32, rsvps[2].id.clone()),
+            (ReservationUpdateType::Update as i32, confirmed.id),
+        ]);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn listen_should_resume_from_consumer_offset() {
+        let manager = setup(&migrated_pool).await;
+        let request = ListenRequest { consumer: "billing".to_string(), ..Default::default() };
+
+        let mut changes = manager.listen(request.clone()).await.unwrap();
+        let rsvps = make_rsvps(&manager, 2).await;
+        // take the first change and ask for the next one, which commits the first
+        next_changes(&mut changes, 2).await;
+        drop(changes);
+
+        let confirmed = manager.change_status(confirm(&rsvps[0])).await.unwrap();
+
+        // the second change was never acknowledged, so it is delivered again
+        let mut changes = manager.listen(request).await.unwrap();
+        let received = next_changes(&mut changes, 2).await;
+        assert_eq!(received, vec![
+            (ReservationUpdateType::Create as i32, rsvps[1].id.clone()),
+            (ReservationUpdateType::Update as i32, confirmed.id),
+        ]);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn changes_should_be_numbered_in_commit_order() {
+        let manager = setup(&migrated_pool).await;
+        let mut tx = migrated_pool.begin().await.unwrap();
+        let slow: Uuid = sqlx::query(r#"INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note)
+        VALUES ('Geng', 'ocean-view-room-713', '[2022-12-25 15:00+00, 2022-12-28 12:00+00)', '') RETURNING id"#)
+            .fetch_one(&mut tx)
+            .await
+            .unwrap()
+            .get(0);
+
+        // an open transaction doesn't hold up other writers
+        let fast = tokio::time::timeout(Duration::from_secs(5),
+                                        manager.reserve(new_rsvp("yage", "ocean-view-room-714", "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700")))
+            .await
+            .expect("reserve shouldn't wait for the open transaction")
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let mut changes = manager.listen(ListenRequest { last_change_id: Some(0), ..Default::default() }).await.unwrap();
+        assert_eq!(next_changes(&mut changes, 2).await, vec![
+            (ReservationUpdateType::Create as i32, fast.id),
+            (ReservationUpdateType::Create as i32, slow.to_string()),
+        ]);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn listen_with_invalid_change_id_should_fail() {
+        let manager = setup(&migrated_pool).await;
+        let request = ListenRequest { last_change_id: Some(-1), ..Default::default() };
+
+        let err = manager.listen(request).await.err().unwrap();
+        assert!(matches!(err, Error::InvalidChangeId(-1)));
+    }
+
+    async fn next_changes(changes: &mut ListenStream, n: usize) -> Vec<(i32, String)> {
+        let mut received = vec![];
+        for _ in 0..n {
+            let change = tokio::time::timeout(Duration::from_secs(5), changes.next())
+                .await
+                .expect("change should arrive")
+                .unwrap()
+                .unwrap();
+            received.push((change.op, change.reservation.unwrap().id));
+        }
+        received
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_should_allow_overlaps_up_to_capacity() {
+        let manager = setup(&migrated_pool).await;
+        manager.create_resource(Resource::new("desk-pool", "desks", "desk", 2)).await.unwrap();
+
+        let first = manager.reserve(new_rsvp("a", "desk-pool", "2022-12-25T09:00:00Z", "2022-12-25T13:00:00Z")).await.unwrap();
+        manager.reserve(new_rsvp("b", "desk-pool", "2022-12-25T15:00:00Z", "2022-12-25T18:00:00Z")).await.unwrap();
+        // overlaps both, but never more than 2 reservations at the same time
+        manager.reserve(new_rsvp("c", "desk-pool", "2022-12-25T11:00:00Z", "2022-12-25T16:00:00Z")).await.unwrap();
+
+        let err = manager.reserve(new_rsvp("d", "desk-pool", "2022-12-25T12:00:00Z", "2022-12-25T12:30:00Z"))
+            .await
+            .unwrap_err();
+        if let Error::ConflictError(ReservationConflictInfo::Parsed(info)) = err {
+            assert_eq!(info.capacity, 2);
+            assert_eq!(info.old.rid, "desk-pool");
+            assert_eq!(info.old.id, Some(first.id));
+            assert_eq!(info.new.start.to_rfc3339(), "2022-12-25T12:00:00+00:00");
+        } else {
+            panic!("should be a parsed conflict")
+        }
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_unknown_or_inactive_resource_should_fail() {
+        let manager = setup(&migrated_pool).await;
+
+        let err = manager.reserve(new_rsvp("a", "no-such-room", "2022-12-25T09:00:00Z", "2022-12-25T13:00:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UnknownResource(rid) if rid == "no-such-room"));
+
+        let mut room = manager.get_resource("ocean-view-room-713".to_string()).await.unwrap();
+        room.active = false;
+        manager.update_resource(room).await.unwrap();
+
+        let err = manager.reserve(new_rsvp("a", "ocean-view-room-713", "2022-12-25T09:00:00Z", "2022-12-25T13:00:00Z"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InactiveResource(rid) if rid == "ocean-view-room-713"));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn hold_should_be_released_unless_confirmed_in_time() {
+        let manager = setup(&migrated_pool).await;
+        let mut changes = manager.listen(ListenRequest::default()).await.unwrap();
+
+        let hold = manager.hold(new_rsvp("a", "room-0", "2022-12-25T09:00:00Z", "2022-12-25T13:00:00Z"),
+                                chrono::Duration::milliseconds(300))
+            .await
+            .unwrap();
+        assert!(hold.expires_at.is_some());
+        // the hold takes the slot until it expires
+        let rsvp = new_rsvp("b", "room-0", "2022-12-25T10:00:00Z", "2022-12-25T11:00:00Z");
+        assert!(matches!(manager.reserve(rsvp.clone()).await, Err(Error::ConflictError(_))));
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+
+        // expired holds neither take the slot nor could be confirmed, even before they are released
+        assert!(matches!(manager.change_status(confirm(&hold)).await, Err(Error::NotFound)));
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+
+        let released = manager.expire().await.unwrap();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].id, hold.id);
+
+        let ops = next_changes(&mut changes, 3).await;
+        assert_eq!(ops, vec![
+            (ReservationUpdateType::Create as i32, hold.id.clone()),
+            (ReservationUpdateType::Create as i32, rsvp.id),
+            (ReservationUpdateType::Expire as i32, hold.id),
+        ]);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn confirmed_hold_should_not_expire() {
+        let manager = setup(&migrated_pool).await;
+        let hold = manager.hold(new_rsvp("a", "room-0", "2022-12-25T09:00:00Z", "2022-12-25T13:00:00Z"),
+                                chrono::Duration::milliseconds(300))
+            .await
+            .unwrap();
+
+        let rsvp = manager.change_status(confirm(&hold)).await.unwrap();
+        assert_eq!(rsvp.status, ReservationStatus::Confirmed as i32);
+        assert_eq!(rsvp.expires_at, None);
 
-        let ret = manager.change_status(rsvp.id).await.unwrap_err();
-        println!("{:?}", ret);
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(manager.expire().await.unwrap().is_empty());
+        assert!(manager.get(hold.id).await.is_ok());
+
+        let err = manager.hold(new_rsvp("a", "room-1", "2022-12-25T09:00:00Z", "2022-12-25T13:00:00Z"),
+                               chrono::Duration::zero())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidHoldDuration(0)));
     }
-}
\ No newline at end of file
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn block_should_release_pending_and_take_whole_resource() {
+        let manager = setup(&migrated_pool).await;
+        manager.create_resource(Resource::new("desk-pool", "desks", "desk", 2)).await.unwrap();
+        let pending = manager.reserve(new_rsvp("a", "desk-pool", "2022-12-25T09:00:00Z", "2022-12-25T10:00:00Z"))
+            .await
+            .unwrap();
+        let mut changes = manager.listen(ListenRequest::default()).await.unwrap();
+
+        let (block, released) = manager.block(block_request("desk-pool", "2022-12-25T08:00:00Z", "2022-12-25T12:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(block.status, ReservationStatus::Blocked as i32);
+        assert_eq!(block.note, "replace carpet");
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].id, pending.id);
+        assert_eq!(released[0].status, ReservationStatus::Cancelled as i32);
+        assert_eq!(released[0].cancel_reason, "blocked: replace carpet");
+        assert_eq!(released[0].cancelled_by, block.user_id);
+        assert_eq!(next_changes(&mut changes, 2).await, vec![
+            (ReservationUpdateType::Update as i32, pending.id),
+            (ReservationUpdateType::Create as i32, block.id.clone()),
+        ]);
+
+        // capacity is left, but nothing overlaps a block
+        let rsvp = new_rsvp("b", "desk-pool", "2022-12-25T11:00:00Z", "2022-12-25T13:00:00Z");
+        match manager.reserve(rsvp.clone()).await {
+            Err(Error::ConflictError(ReservationConflictInfo::Parsed(info))) => assert_eq!(info.old.id, Some(block.id.clone())),
+            ret => panic!("should conflict with the block, got {:?}", ret),
+        }
+
+        let query = ReservationQueryBuilder::default()
+            .resource_id("desk-pool")
+            .status(ReservationStatus::Blocked as i32)
+            .build()
+            .unwrap();
+        assert_eq!(manager.query(query).await.unwrap(), vec![block.clone()]);
+
+        let block = manager.unblock(block.id).await.unwrap();
+        assert_eq!(block.status, ReservationStatus::Released as i32);
+        assert_eq!(block.note, "replace carpet");
+        manager.reserve(rsvp).await.unwrap();
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn block_overlapping_confirmed_should_fail() {
+        let manager = setup(&migrated_pool).await;
+        let pending = manager.reserve(new_rsvp("a", "room-0", "2022-12-25T09:00:00Z", "2022-12-25T10:00:00Z"))
+            .await
+            .unwrap();
+        let confirmed = manager.reserve(new_rsvp("b", "room-0", "2022-12-25T10:00:00Z", "2022-12-25T11:00:00Z"))
+            .await
+            .unwrap();
+        manager.change_status(confirm(&confirmed)).await.unwrap();
+
+        match manager.block(block_request("room-0", "2022-12-25T08:00:00Z", "2022-12-25T12:00:00Z")).await {
+            Err(Error::ConflictError(ReservationConflictInfo::Parsed(info))) => assert_eq!(info.old.id, Some(confirmed.id.clone())),
+            ret => panic!("should conflict with the confirmed reservation, got {:?}", ret),
+        }
+        // nothing is released when the block fails
+        assert!(manager.get(pending.id).await.is_ok());
+
+        let request = BlockRequest { reason: " ".to_string(), ..block_request("room-1", "2022-12-25T08:00:00Z", "2022-12-25T12:00:00Z") };
+        assert!(matches!(manager.block(request).await, Err(Error::MissingBlockReason)));
+        assert!(matches!(manager.unblock(confirmed.id).await,
+            Err(Error::InvalidTransition { from: ReservationStatus::Confirmed, to: ReservationStatus::Released })));
+    }
+
+    fn block_request(rid: &str, start: &str, end: &str) -> BlockRequest {
+        BlockRequest {
+            resource_id: rid.to_string(),
+            user_id: "facilities".to_string(),
+            start: Some(start.parse().unwrap()),
+            end: Some(end.parse().unwrap()),
+            reason: "replace carpet".to_string(),
+        }
+    }
+
+    /// manager with the resources used by the tests registered
+    async fn setup(pool: &PgPool) -> ReservationManager {
+        let manager = ReservationManager::new(pool.clone());
+        let mut rids = vec!["ocean-view-room-713".to_string(), "ocean-view-room-714".to_string()];
+        rids.extend((0..12).map(|i| format!("room-{}", i)));
+        for rid in rids {
+            manager.create_resource(Resource::new(rid, "", "room", 1)).await.unwrap();
+        }
+        manager
+    }
+
+    async fn make_rsvps(manager: &ReservationManager, n: usize) -> Vec<Reservation> {
+        let mut rsvps = Vec::with_capacity(n);
+        for i in 0..n {
+            let rsvp = new_rsvp("Geng", &format!("room-{}", i), "2022-12-25T15:00:00-0700", "2022-12-28T12:00:00-0700");
+            rsvps.push(manager.reserve(rsvp).await.unwrap());
+        }
+        rsvps
+    }
+
+    fn free_slot(rid: &str, start: &str, end: &str) -> FreeSlot {
+        FreeSlot { resource_id: rid.to_string(), start: Some(start.parse().unwrap()), end: Some(end.parse().unwrap()) }
+    }
+
+    fn confirm(rsvp: &Reservation) -> ConfirmRequest {
+        ConfirmRequest { id: rsvp.id.clone(), version: rsvp.version }
+    }
+
+    fn update_note(rsvp: &Reservation, note: &str) -> UpdateRequest {
+        UpdateRequest { id: rsvp.id.clone(), note: note.to_string(), version: rsvp.version }
+    }
+
+    fn new_rsvp(uid: &str, rid: &str, start: &str, end: &str) -> Reservation {
+        Reservation::new_pending(uid, rid, start.parse().unwrap(), end.parse().unwrap(), "ok")
+    }
+}
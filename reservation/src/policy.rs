@@ -0,0 +1,195 @@
+use async_trait::async_trait;
+use chrono::Duration;
+
+use abi::{Error, Policy};
+
+use crate::{Policies, ReservationManager, ResourceId};
+
+#[async_trait]
+impl Policies for ReservationManager {
+    async fn set_policy(&self, policy: Policy) -> Result<Policy, Error> {
+        policy.validate()?;
+        if !policy.resource_id.is_empty() {
+            let found = sqlx::query("SELECT 1 FROM rsvp.resources WHERE id = $1")
+                .bind(&policy.resource_id)
+                .fetch_optional(&self.pool)
+                .await?;
+            if found.is_none() {
+                return Err(Error::UnknownResource(policy.resource_id));
+            }
+        }
+
+        let (max_duration, min_lead_time, max_horizon) = policy.get_limits()?;
+        // zero stands for no allowed hours, not the whole day
+        let (opens_at, closes_at) = match policy.has_allowed_hours() {
+            true => policy.get_allowed_hours()?,
+            false => (Duration::zero(), Duration::zero()),
+        };
+        let policy: Policy = sqlx::query_as(r#"INSERT INTO rsvp.policies
+        (resource_id, max_active_reservations, max_duration, min_lead_time, max_horizon, opens_at, closes_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (resource_id) DO UPDATE SET max_active_reservations = EXCLUDED.max_active_reservations,
+        max_duration = EXCLUDED.max_duration, min_lead_time = EXCLUDED.min_lead_time, max_horizon = EXCLUDED.max_horizon,
+        opens_at = EXCLUDED.opens_at, closes_at = EXCLUDED.closes_at, updated_at = now()
+        RETURNING *"#)
+            .bind(&policy.resource_id)
+            .bind(policy.max_active_reservations)
+            .bind(max_duration)
+            .bind(min_lead_time)
+            .bind(max_horizon)
+            .bind(opens_at)
+            .bind(closes_at)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(policy)
+    }
+
+    async fn get_policy(&self, id: ResourceId) -> Result<Policy, Error> {
+        let policy: Policy = sqlx::query_as("SELECT * FROM rsvp.policies WHERE resource_id = $1")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(policy)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, DurationRound, Utc};
+
+    use abi::{PolicyRule, RescheduleRequest, Reservation, ReservationSeries, ReserveAnyRequest, Resource};
+
+    use crate::{Resources, Rsvp, Series};
+    use super::*;
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_should_enforce_policies() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        for rid in ["room-1", "room-2"] {
+            manager.create_resource(Resource::new(rid, "", "room", 1)).await.unwrap();
+        }
+        let hours = |h: i64| Some(prost_types::Duration { seconds: h * 3600, nanos: 0 });
+        manager.set_policy(Policy { max_active_reservations: 2, max_horizon: hours(24 * 7), ..Default::default() })
+            .await
+            .unwrap();
+        let policy = manager.set_policy(Policy { resource_id: "room-1".to_string(), max_duration: hours(2), ..Default::default() })
+            .await
+            .unwrap();
+        assert_eq!(manager.get_policy("room-1".to_string()).await.unwrap(), policy);
+
+        // tomorrow from 10 o'clock on, whole hours
+        let day = (Utc::now() + Duration::days(1)).duration_trunc(Duration::days(1)).unwrap();
+        let rsvp = |rid: &str, start: i64, end: i64| {
+            let start: DateTime<Utc> = day + Duration::hours(start);
+            let end: DateTime<Utc> = day + Duration::hours(end);
+            Reservation::new_pending("gyg", rid, start.into(), end.into(), "")
+        };
+
+        assert_eq!(rule(manager.reserve(rsvp("room-1", 10, 13)).await), PolicyRule::MaxDuration);
+        manager.reserve(rsvp("room-1", 10, 12)).await.unwrap();
+        // the policy of room-1 doesn't apply to room-2
+        manager.reserve(rsvp("room-2", 10, 13)).await.unwrap();
+        assert_eq!(rule(manager.reserve(rsvp("room-2", 14, 15)).await), PolicyRule::MaxActiveReservations);
+        let far_ahead = Reservation { user_id: "tyr".to_string(), ..rsvp("room-2", 24 * 8, 24 * 8 + 1) };
+        assert_eq!(rule(manager.reserve(far_ahead).await), PolicyRule::MaxHorizon);
+
+        // a quota alone doesn't keep a stay within a day
+        let stay = Reservation { user_id: "tyr".to_string(), ..rsvp("room-1", 15, 24 * 3 + 11) };
+        manager.set_policy(Policy { resource_id: "room-1".to_string(), max_active_reservations: 1, ..Default::default() })
+            .await
+            .unwrap();
+        manager.reserve(stay).await.unwrap();
+
+        let err = manager.set_policy(Policy { resource_id: "room-404".to_string(), ..Default::default() }).await.unwrap_err();
+        assert!(matches!(err, Error::UnknownResource(rid) if rid == "room-404"));
+        assert!(matches!(manager.get_policy("room-2".to_string()).await, Err(Error::NotFound)));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn every_writer_should_enforce_policies() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager.create_resource(Resource::new("room-1", "", "room", 1)).await.unwrap();
+        let hours = |h: i64| Some(prost_types::Duration { seconds: h * 3600, nanos: 0 });
+        manager.set_policy(Policy { max_active_reservations: 3, max_horizon: hours(24 * 30), ..Default::default() })
+            .await
+            .unwrap();
+        manager.set_policy(Policy {
+            resource_id: "room-1".to_string(),
+            max_duration: hours(2),
+            opens_at: hours(8),
+            closes_at: hours(20),
+            ..Default::default()
+        })
+            .await
+            .unwrap();
+
+        let day = (Utc::now() + Duration::days(1)).duration_trunc(Duration::days(1)).unwrap();
+        let at = |h: i64| -> DateTime<Utc> { day + Duration::hours(h) };
+        let ts = |h: i64| prost_types::Timestamp { seconds: at(h).timestamp(), nanos: 0 };
+
+        // a daily series for a year takes more than the quota
+        let series = ReservationSeries::new_pending("gyg", "room-1", at(10).into(), at(11).into(), "FREQ=DAILY;COUNT=365", "");
+        assert_eq!(rule(manager.reserve_series(series).await), PolicyRule::MaxActiveReservations);
+
+        let request = ReserveAnyRequest {
+            reservation: Some(Reservation::new_pending("gyg", "", at(10).into(), at(13).into(), "")),
+            resource_ids: vec!["room-1".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(rule(manager.reserve_any(request).await), PolicyRule::MaxDuration);
+
+        // moving a reservation is checked as much as making it
+        let rsvp = manager.reserve(Reservation::new_pending("gyg", "room-1", at(10).into(), at(11).into(), ""))
+            .await
+            .unwrap();
+        let reschedule = |start: i64, end: i64| RescheduleRequest {
+            id: rsvp.id.clone(),
+            version: rsvp.version,
+            start: Some(ts(start)),
+            end: Some(ts(end)),
+            ..Default::default()
+        };
+        assert_eq!(rule(manager.reschedule(reschedule(10, 13)).await), PolicyRule::MaxDuration);
+        assert_eq!(rule(manager.reschedule(reschedule(19, 21)).await), PolicyRule::AllowedHours);
+        assert_eq!(rule(manager.reschedule(reschedule(24 * 40 + 10, 24 * 40 + 11)).await), PolicyRule::MaxHorizon);
+        let moved = manager.reschedule(reschedule(12, 14)).await.unwrap();
+        assert_eq!(moved.start, Some(ts(12)));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn allowed_hours_should_follow_time_zone_of_resource() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let resource = Resource { time_zone: "Asia/Tokyo".to_string(), ..Resource::new("room-1", "", "room", 1) };
+        manager.create_resource(resource).await.unwrap();
+        let hours = |h: i64| Some(prost_types::Duration { seconds: h * 3600, nanos: 0 });
+        manager.set_policy(Policy { opens_at: hours(8), closes_at: hours(20), ..Default::default() })
+            .await
+            .unwrap();
+
+        // Tokyo is 9 hours ahead of UTC all year
+        let day = (Utc::now() + Duration::days(1)).duration_trunc(Duration::days(1)).unwrap();
+        let rsvp = |start: i64, end: i64| {
+            let start: DateTime<Utc> = day + Duration::hours(start);
+            let end: DateTime<Utc> = day + Duration::hours(end);
+            Reservation::new_pending("gyg", "room-1", start.into(), end.into(), "")
+        };
+        manager.reserve(rsvp(0, 1)).await.unwrap();
+        manager.reserve(rsvp(10, 11)).await.unwrap();
+        assert_eq!(rule(manager.reserve(rsvp(12, 13)).await), PolicyRule::AllowedHours);
+    }
+
+    fn rule<T: std::fmt::Debug>(ret: Result<T, Error>) -> PolicyRule {
+        match ret {
+            Err(Error::PolicyViolation { rule, .. }) => rule,
+            ret => panic!("expect policy violation, got {:?}", ret),
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use async_trait::async_trait;
+use sqlx::Row;
+
+use abi::{Error, Resource};
+
+use crate::{ReservationManager, ResourceId, Resources};
+
+#[async_trait]
+impl Resources for ReservationManager {
+    async fn create_resource(&self, mut resource: Resource) -> Result<Resource, Error> {
+        if resource.capacity == 0 {
+            resource.capacity = 1;
+        }
+        resource.validate()?;
+        self.check_time_zone(&resource.time_zone).await?;
+        let (buffer_before, buffer_after) = resource.get_buffers()?;
+        let (slot, min_duration, max_duration) = resource.get_slot_limits()?;
+
+        let resource: Resource = sqlx::query_as(r#"INSERT INTO rsvp.resources
+        (id, name, resource_type, capacity, active, buffer_before, buffer_after, time_zone, opening_hours, holidays,
+        slot, min_duration, max_duration, rounding)
+        VALUES ($1, $2, $3, $4, true, $5, $6, $7, $8, $9, $10, $11, $12, $13) RETURNING *"#)
+            .bind(&resource.id)
+            .bind(&resource.name)
+            .bind(&resource.resource_type)
+            .bind(resource.capacity)
+            .bind(buffer_before)
+            .bind(buffer_after)
+            .bind(&resource.time_zone)
+            .bind(resource.get_opening_hours()?)
+            .bind(resource.get_holidays()?)
+            .bind(slot)
+            .bind(min_duration)
+            .bind(max_duration)
+            .bind(resource.get_rounding())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(resource)
+    }
+
+    async fn update_resource(&self, resource: Resource) -> Result<Resource, Error> {
+        resource.validate()?;
+        self.check_time_zone(&resource.time_zone).await?;
+        let (buffer_before, buffer_after) = resource.get_buffers()?;
+        let (slot, min_duration, max_duration) = resource.get_slot_limits()?;
+
+        let mut tx = self.begin().await?;
+        // lock the resource like the capacity check does, so no reservation sneaks in meanwhile
+        let locked = sqlx::query("SELECT id FROM rsvp.resources WHERE id = $1 FOR UPDATE")
+            .bind(&resource.id)
+            .fetch_optional(&mut tx)
+            .await?;
+        if locked.is_none() {
+            return Err(Error::NotFound);
+        }
+        // the capacity can't go below what the reservations still to come already take
+        let peak: i64 = sqlx::query(r#"WITH b AS (SELECT * FROM rsvp.buffers($1)),
+        live AS (SELECT r.id, r.timespan FROM rsvp.reservations r
+                 WHERE r.resource_id = $1 AND upper(r.timespan) > now()
+                   AND rsvp.holds_window(r) AND NOT rsvp.is_exclusive(r))
+        SELECT coalesce(max(n), 0) FROM (
+            SELECT count(*) AS n FROM live o, live r, b
+            WHERE rsvp.pad(r.timespan, b.pre, b.post) @> (lower(o.timespan) - b.pre)
+            GROUP BY o.id) peaks"#)
+            .bind(&resource.id)
+            .fetch_one(&mut tx)
+            .await?
+            .get(0);
+        if (resource.capacity as i64) < peak {
+            return Err(Error::InvalidCapacity(resource.capacity));
+        }
+
+        // opening hours, holidays and slots apply to reservations made from now on, existing ones are kept
+        let resource: Resource = sqlx::query_as(r#"UPDATE rsvp.resources
+        SET name = $2, resource_type = $3, capacity = $4, active = $5, buffer_before = $6, buffer_after = $7,
+        time_zone = $8, opening_hours = $9, holidays = $10,
+        slot = $11, min_duration = $12, max_duration = $13, rounding = $14
+        WHERE id = $1 RETURNING *"#)
+            .bind(&resource.id)
+            .bind(&resource.name)
+            .bind(&resource.resource_type)
+            .bind(resource.capacity)
+            .bind(resource.active)
+            .bind(buffer_before)
+            .bind(buffer_after)
+            .bind(&resource.time_zone)
+            .bind(resource.get_opening_hours()?)
+            .bind(resource.get_holidays()?)
+            .bind(slot)
+            .bind(min_duration)
+            .bind(max_duration)
+            .bind(resource.get_rounding())
+            .fetch_one(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(resource)
+    }
+
+    async fn get_resource(&self, id: ResourceId) -> Result<Resource, Error> {
+        let resource: Resource = sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(resource)
+    }
+}
+
+impl ReservationManager {
+    /// postgres knows the time zones, empty means UTC
+    async fn check_time_zone(&self, time_zone: &str) -> Result<(), Error> {
+        if time_zone.is_empty() {
+            return Ok(());
+        }
+
+        let found = sqlx::query("SELECT 1 FROM pg_timezone_names WHERE name = $1")
+            .bind(time_zone)
+            .fetch_optional(&self.pool)
+            .await?;
+        match found {
+            Some(_) => Ok(()),
+            None => Err(Error::InvalidTimeZone(time_zone.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use abi::Reservation;
+
+    use crate::Rsvp;
+    use super::*;
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn create_resource_should_work() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let resource = Resource { capacity: 0, active: false, ..Resource::new("room-1", "Ocean", "meeting-room", 0) };
+
+        let resource = manager.create_resource(resource).await.unwrap();
+        assert_eq!(resource.capacity, 1);
+        assert!(resource.active);
+
+        let ret = manager.get_resource("room-1".to_string()).await.unwrap();
+        assert_eq!(ret, resource);
+
+        let err = manager.create_resource(Resource::new("room-1", "Ocean", "meeting-room", 1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ResourceAlreadyExists(id) if id == "room-1"));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn update_resource_should_work() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager.create_resource(Resource::new("desk-pool", "desks", "desk", 10)).await.unwrap();
+
+        let resource = Resource {
+            capacity: 12,
+            active: false,
+            buffer_after: Some(prost_types::Duration { seconds: 1800, nanos: 0 }),
+            time_zone: "Europe/Berlin".to_string(),
+            opening_hours: vec![abi::OpeningHours {
+                weekday: abi::Weekday::Monday as i32,
+                opens_at: Some(prost_types::Duration { seconds: 8 * 3600, nanos: 0 }),
+                closes_at: Some(prost_types::Duration { seconds: 20 * 3600, nanos: 0 }),
+            }],
+            holidays: vec!["2023-01-06".to_string()],
+            ..Resource::new("desk-pool", "open desks", "desk", 12)
+        };
+        let ret = manager.update_resource(resource.clone()).await.unwrap();
+        assert_eq!(ret, resource);
+
+        let err = manager.update_resource(Resource { time_zone: "Mars/Olympus".to_string(), ..resource.clone() }).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidTimeZone(tz) if tz == "Mars/Olympus"));
+
+        let err = manager.update_resource(Resource { capacity: 0, ..resource.clone() }).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidCapacity(0)));
+
+        let err = manager.update_resource(Resource { id: "nope".to_string(), ..resource }).await.unwrap_err();
+        assert!(matches!(err, Error::NotFound));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn capacity_should_not_go_below_peak_usage() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager.create_resource(Resource::new("desk-pool", "desks", "desk", 3)).await.unwrap();
+        // two overlap from 10 to 11 and two others from 12 to 13, but never more than two at once
+        for (start, end) in [("2099-01-03T09:00:00Z", "2099-01-03T11:00:00Z"),
+                             ("2099-01-03T10:00:00Z", "2099-01-03T12:00:00Z"),
+                             ("2099-01-03T12:00:00Z", "2099-01-03T13:00:00Z"),
+                             ("2099-01-03T12:00:00Z", "2099-01-03T13:00:00Z")] {
+            let rsvp = Reservation::new_pending("gyg", "desk-pool", start.parse().unwrap(), end.parse().unwrap(), "");
+            manager.reserve(rsvp).await.unwrap();
+        }
+
+        let err = manager.update_resource(Resource::new("desk-pool", "desks", "desk", 1)).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidCapacity(1)));
+        assert_eq!(manager.get_resource("desk-pool".to_string()).await.unwrap().capacity, 3);
+
+        let ret = manager.update_resource(Resource::new("desk-pool", "desks", "desk", 2)).await.unwrap();
+        assert_eq!(ret.capacity, 2);
+    }
+}
@@ -0,0 +1,384 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::postgres::types::PgRange;
+use sqlx::types::Uuid;
+use sqlx::{Postgres, Row, Transaction};
+
+use abi::{CancelSeriesRequest, Error, Reservation, ReservationSeries, ReservationStatus, UpdateSeriesRequest};
+
+use crate::manager::{insert_reservation, string_to_option, InsertOptions};
+use crate::{ReservationManager, Series, SeriesId};
+
+#[async_trait]
+impl Series for ReservationManager {
+    async fn reserve_series(&self, series: ReservationSeries) -> Result<(ReservationSeries, Vec<Reservation>), Error> {
+        series.validate()?;
+
+        let mut tx = self.begin().await?;
+        let (series, rsvps) = match insert_series(&mut tx, series).await {
+            Ok(ret) => ret,
+            Err(e) => {
+                // release the connection before looking up the holders on the pool
+                tx.rollback().await?;
+                return Err(self.fill_conflict_ids(e).await);
+            }
+        };
+        tx.commit().await?;
+
+        Ok((series, rsvps))
+    }
+
+    async fn update_series(&self, request: UpdateSeriesRequest) -> Result<(ReservationSeries, Vec<Reservation>), Error> {
+        let from = request.get_from()?;
+        let id = parse_series_id(request.id)?;
+        let series = request.series.unwrap_or_default();
+        series.validate()?;
+
+        let mut tx = self.begin().await?;
+        // only the owner's series could be cut short, truncate_series locks it for the rest of tx
+        let owner: String = sqlx::query("SELECT user_id FROM rsvp.reservation_series WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut tx)
+            .await?
+            .get(0);
+        if owner != series.user_id {
+            return Err(Error::InvalidUserId(series.user_id));
+        }
+        truncate_series(&mut tx, id, Some(from), &request.cancelled_by, "series updated").await?;
+        let (series, rsvps) = match insert_series(&mut tx, series).await {
+            Ok(ret) => ret,
+            Err(e) => {
+                // release the connection before looking up the holders on the pool
+                tx.rollback().await?;
+                return Err(self.fill_conflict_ids(e).await);
+            }
+        };
+        tx.commit().await?;
+
+        Ok((series, rsvps))
+    }
+
+    async fn cancel_series(&self, request: CancelSeriesRequest) -> Result<Vec<Reservation>, Error> {
+        let from = request.get_from()?;
+        let id = parse_series_id(request.id)?;
+
+        let mut tx = self.begin().await?;
+        let rsvps = truncate_series(&mut tx, id, from, &request.cancelled_by, &request.reason).await?;
+        tx.commit().await?;
+
+        Ok(rsvps)
+    }
+
+    async fn get_series(&self, id: SeriesId) -> Result<ReservationSeries, Error> {
+        let id = parse_series_id(id)?;
+
+        let series: ReservationSeries = sqlx::query_as("SELECT * FROM rsvp.reservation_series WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(series)
+    }
+}
+
+impl ReservationManager {
+    /// the conflicting reservations of a series are looked up after the transaction is rolled back,
+    /// so other occurrences of the same series are not reported as the holder
+    async fn fill_conflict_ids(&self, err: Error) -> Error {
+        match err {
+            Error::SeriesConflict(conflicts) => {
+                let mut filled = Vec::with_capacity(conflicts.len());
+                for info in conflicts {
+                    filled.push(self.fill_conflict_id(info).await);
+                }
+                Error::SeriesConflict(filled)
+            }
+            e => e,
+        }
+    }
+}
+
+/// insert the series and all its occurrences, every conflicting occurrence is reported
+async fn insert_series(tx: &mut Transaction<'_, Postgres>, series: ReservationSeries)
+                       -> Result<(ReservationSeries, Vec<Reservation>), Error> {
+    let occurrences = series.occurrences()?;
+    if occurrences.is_empty() {
+        return Err(Error::InvalidRecurrenceRule(format!("no occurrence left: {}", series.rrule)));
+    }
+
+    let status = ReservationStatus::from_i32(series.status)
+        .unwrap_or(ReservationStatus::Pending);
+    let timespan: PgRange<DateTime<Utc>> = series.get_timespan()?.into();
+
+    let series: ReservationSeries = sqlx::query_as(r#"INSERT INTO rsvp.reservation_series
+    (user_id, resource_id, status, timespan, note, rrule, exdates, time_zone)
+    VALUES ($1, $2, $3::rsvp.reservation_status, $4, $5, $6, $7, $8) RETURNING *"#)
+        .bind(&series.user_id)
+        .bind(&series.resource_id)
+        .bind(status.to_string())
+        .bind(timespan)
+        .bind(&series.note)
+        .bind(&series.rrule)
+        .bind(series.get_exdates()?)
+        .bind(&series.time_zone)
+        .fetch_one(&mut *tx)
+        .await?;
+    let id = parse_series_id(series.id.clone())?;
+
+    let mut rsvps = Vec::with_capacity(occurrences.len());
+    let mut conflicts = vec![];
+    for occurrence in occurrences {
+        let rsvp = Reservation {
+            status: status as i32,
+            ..Reservation::new_pending(&series.user_id, &series.resource_id, occurrence.start.into(), occurrence.end.into(), &series.note)
+        };
+
+        // a conflict only rolls back its own insert, keep going to report the other conflicts
+        match insert_reservation(tx, &rsvp, InsertOptions { series_id: Some(id), ..Default::default() }).await {
+            Ok(rsvp) => rsvps.push(rsvp),
+            Err(Error::ConflictError(info)) => conflicts.push(info),
+            Err(e) => return Err(e),
+        }
+    }
+
+    if !conflicts.is_empty() {
+        return Err(Error::SeriesConflict(conflicts));
+    }
+
+    Ok((series, rsvps))
+}
+
+/// cancel the occurrences starting at or after from (all of them if none), and end the series before from.
+/// The series itself is cancelled once none of its occurrences is left
+async fn truncate_series(tx: &mut Transaction<'_, Postgres>, id: Uuid, from: Option<DateTime<Utc>>,
+                         cancelled_by: &str, reason: &str) -> Result<Vec<Reservation>, Error> {
+    let series: ReservationSeries = sqlx::query_as("SELECT * FROM rsvp.reservation_series WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let rsvps: Vec<Reservation> = sqlx::query_as(r#"WITH cancelled AS (
+        UPDATE rsvp.reservations
+        SET status = 'cancelled', cancelled_at = now(), cancelled_by = $3, cancel_reason = $4
+        WHERE series_id = $1 AND ($2::timestamptz IS NULL OR lower(timespan) >= $2)
+        AND status IN ('pending', 'confirmed') RETURNING *
+    ) SELECT * FROM cancelled ORDER BY lower(timespan), id"#)
+        .bind(id)
+        .bind(from)
+        .bind(string_to_option(cancelled_by))
+        .bind(string_to_option(reason))
+        .fetch_all(&mut *tx)
+        .await?;
+
+    let left: i64 = sqlx::query("SELECT count(*) FROM rsvp.reservations WHERE series_id = $1 AND status IN ('pending', 'confirmed')")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?
+        .get(0);
+
+    match from {
+        Some(from) if left > 0 => {
+            // only end the rule early if it goes beyond from, replacing COUNT with UNTIL could extend it otherwise
+            if series.occurrences()?.iter().any(|o| o.start >= from) {
+                let mut rule = series.get_rule()?;
+                let until = from - Duration::seconds(1);
+                rule.count = None;
+                rule.until = Some(rule.until.map_or(until, |u| u.min(until)));
+
+                sqlx::query("UPDATE rsvp.reservation_series SET rrule = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(rule.to_string())
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+        _ => {
+            sqlx::query("UPDATE rsvp.reservation_series SET status = 'cancelled' WHERE id = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    Ok(rsvps)
+}
+
+fn parse_series_id(id: SeriesId) -> Result<Uuid, Error> {
+    Uuid::parse_str(&id).map_err(|_| Error::InvalidSeriesId(id))
+}
+
+#[cfg(test)]
+mod test {
+    use abi::{ReservationQueryBuilder, Resource};
+    use prost_types::Timestamp;
+
+    use crate::{Resources, Rsvp};
+    use super::*;
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_series_should_reserve_every_occurrence() {
+        let manager = setup(&migrated_pool).await;
+
+        let (series, rsvps) = manager.reserve_series(weekly_series(4)).await.unwrap();
+        assert_ne!(series.id, "");
+        assert_eq!(rsvps.len(), 4);
+        assert!(rsvps.iter().all(|r| r.series_id == series.id && r.resource_id == "room-1"));
+        assert_eq!(rsvps[3].start, Some(ts("2023-01-24T10:00:00Z")));
+        assert_eq!(rsvps[3].end, Some(ts("2023-01-24T11:00:00Z")));
+
+        assert_eq!(manager.get_series(series.id.clone()).await.unwrap(), series);
+        assert_eq!(manager.get(rsvps[0].id.clone()).await.unwrap().series_id, series.id);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn reserve_series_should_report_every_conflict_and_reserve_nothing() {
+        let manager = setup(&migrated_pool).await;
+        let second = manager.reserve(Reservation::new_pending("other", "room-1",
+                                                              "2023-01-10T10:30:00Z".parse().unwrap(),
+                                                              "2023-01-10T12:00:00Z".parse().unwrap(), ""))
+            .await
+            .unwrap();
+        manager.reserve(Reservation::new_pending("other", "room-1",
+                                                 "2023-01-24T09:00:00Z".parse().unwrap(),
+                                                 "2023-01-24T10:30:00Z".parse().unwrap(), ""))
+            .await
+            .unwrap();
+
+        let err = manager.reserve_series(weekly_series(4)).await.unwrap_err();
+        let conflicts = match err {
+            Error::SeriesConflict(conflicts) => conflicts,
+            e => panic!("expect series conflict, got {:?}", e),
+        };
+        assert_eq!(conflicts.len(), 2);
+        match &conflicts[0] {
+            abi::ReservationConflictInfo::Parsed(conflict) => {
+                assert_eq!(conflict.new.start.to_rfc3339(), "2023-01-10T10:00:00+00:00");
+                assert_eq!(conflict.old.id, Some(second.id));
+            }
+            info => panic!("expect parsed conflict, got {:?}", info),
+        }
+
+        let query = ReservationQueryBuilder::default().resource_id("room-1").build().unwrap();
+        assert_eq!(manager.query(query).await.unwrap().len(), 2);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn cancel_series_should_cancel_this_and_following() {
+        let manager = setup(&migrated_pool).await;
+        let (series, _) = manager.reserve_series(weekly_series(5)).await.unwrap();
+
+        let cancelled = manager.cancel_series(CancelSeriesRequest {
+            id: series.id.clone(),
+            from: Some(ts("2023-01-17T10:00:00Z")),
+            cancelled_by: "gyg".to_string(),
+            reason: "project ended".to_string(),
+        }).await.unwrap();
+        assert_eq!(cancelled.len(), 3);
+        assert_eq!(cancelled[0].start, Some(ts("2023-01-17T10:00:00Z")));
+        assert!(cancelled.iter().all(|r| r.status == ReservationStatus::Cancelled as i32 && r.cancel_reason == "project ended"));
+
+        let truncated = manager.get_series(series.id.clone()).await.unwrap();
+        assert_eq!(truncated.rrule, "FREQ=WEEKLY;UNTIL=20230117T095959Z;BYDAY=TU");
+        assert_eq!(truncated.occurrences().unwrap().len(), 2);
+
+        let cancelled = manager.cancel_series(CancelSeriesRequest { id: series.id.clone(), ..Default::default() })
+            .await
+            .unwrap();
+        assert_eq!(cancelled.len(), 2);
+        // cancelled occurrences are kept along with their series
+        assert_eq!(manager.get_series(series.id.clone()).await.unwrap().status, ReservationStatus::Cancelled as i32);
+        assert_eq!(manager.get(cancelled[0].id.clone()).await.unwrap().series_id, series.id);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn update_series_should_replace_this_and_following() {
+        let manager = setup(&migrated_pool).await;
+        let (series, rsvps) = manager.reserve_series(weekly_series(5)).await.unwrap();
+
+        // from the third occurrence on, meet on Thursday afternoon instead
+        let new = ReservationSeries::new_pending("gyg", "room-1",
+                                                 "2023-01-19T14:00:00Z".parse().unwrap(),
+                                                 "2023-01-19T15:00:00Z".parse().unwrap(),
+                                                 "FREQ=WEEKLY;COUNT=3", "moved");
+        // nobody else could cut the series short
+        let err = manager.update_series(UpdateSeriesRequest {
+            id: series.id.clone(),
+            from: rsvps[2].start.clone(),
+            series: Some(ReservationSeries { user_id: "other".to_string(), ..new.clone() }),
+            cancelled_by: "other".to_string(),
+        }).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidUserId(uid) if uid == "other"));
+
+        let (new, new_rsvps) = manager.update_series(UpdateSeriesRequest {
+            id: series.id.clone(),
+            from: rsvps[2].start.clone(),
+            series: Some(new),
+            cancelled_by: "front-desk".to_string(),
+        }).await.unwrap();
+        assert_ne!(new.id, series.id);
+        assert_eq!(new_rsvps.len(), 3);
+        assert!(new_rsvps.iter().all(|r| r.note == "moved" && r.series_id == new.id));
+
+        let old = manager.get_series(series.id.clone()).await.unwrap();
+        assert_eq!(old.occurrences().unwrap().len(), 2);
+        assert_eq!(manager.get(rsvps[1].id.clone()).await.unwrap().status, ReservationStatus::Pending as i32);
+        let replaced = manager.get(rsvps[2].id.clone()).await.unwrap();
+        assert_eq!(replaced.status, ReservationStatus::Cancelled as i32);
+        assert_eq!(replaced.cancel_reason, "series updated");
+        assert_eq!(replaced.cancelled_by, "front-desk");
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn update_series_with_conflict_should_keep_the_old_series() {
+        let manager = setup(&migrated_pool).await;
+        let (series, rsvps) = manager.reserve_series(weekly_series(3)).await.unwrap();
+        manager.reserve(Reservation::new_pending("other", "room-1",
+                                                 "2023-01-18T10:00:00Z".parse().unwrap(),
+                                                 "2023-01-18T11:00:00Z".parse().unwrap(), ""))
+            .await
+            .unwrap();
+
+        let new = ReservationSeries::new_pending("gyg", "room-1",
+                                                 "2023-01-11T10:00:00Z".parse().unwrap(),
+                                                 "2023-01-11T11:00:00Z".parse().unwrap(),
+                                                 "FREQ=WEEKLY;COUNT=2", "");
+        let err = manager.update_series(UpdateSeriesRequest {
+            id: series.id.clone(),
+            from: rsvps[1].start.clone(),
+            series: Some(new),
+            ..Default::default()
+        }).await.unwrap_err();
+        assert!(matches!(err, Error::SeriesConflict(conflicts) if conflicts.len() == 1));
+
+        assert_eq!(manager.get_series(series.id).await.unwrap().rrule, "FREQ=WEEKLY;BYDAY=TU;COUNT=3");
+        assert!(manager.get(rsvps[2].id.clone()).await.is_ok());
+    }
+
+    async fn setup(pool: &sqlx::PgPool) -> ReservationManager {
+        let manager = ReservationManager::new(pool.clone());
+        manager.create_resource(Resource::new("room-1", "", "room", 1)).await.unwrap();
+        manager
+    }
+
+    /// one hour every Tuesday from 2023-01-03
+    fn weekly_series(count: u32) -> ReservationSeries {
+        ReservationSeries::new_pending("gyg", "room-1",
+                                       "2023-01-03T10:00:00Z".parse().unwrap(),
+                                       "2023-01-03T11:00:00Z".parse().unwrap(),
+                                       format!("FREQ=WEEKLY;BYDAY=TU;COUNT={}", count), "weekly sync")
+    }
+
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+}
@@ -0,0 +1,316 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::types::PgRange;
+use sqlx::types::Uuid;
+use sqlx::Row;
+
+use abi::{Error, WaitlistEntry};
+
+use crate::{ReservationManager, Waitlist, WaitlistEntryId};
+
+#[async_trait]
+impl Waitlist for ReservationManager {
+    async fn join_waitlist(&self, entry: WaitlistEntry) -> Result<WaitlistEntry, Error> {
+        entry.validate()?;
+        let timespan: PgRange<DateTime<Utc>> = entry.get_timespan()?.into();
+
+        let mut tx = self.begin().await?;
+        let active: Option<bool> = sqlx::query("SELECT active FROM rsvp.resources WHERE id = $1")
+            .bind(&entry.resource_id)
+            .fetch_optional(&mut tx)
+            .await?
+            .map(|row| row.get(0));
+        match active {
+            None => return Err(Error::UnknownResource(entry.resource_id)),
+            Some(false) => return Err(Error::InactiveResource(entry.resource_id)),
+            Some(true) => {}
+        }
+
+        // the window is snapped to the slots of the resource like a reservation would be
+        let row = sqlx::query(r#"INSERT INTO rsvp.waitlist (user_id, resource_id, timespan, note, hold)
+        VALUES ($1, $2, $3, $4, $5) RETURNING id, timespan"#)
+            .bind(&entry.user_id)
+            .bind(&entry.resource_id)
+            .bind(timespan)
+            .bind(&entry.note)
+            .bind(entry.get_hold()?)
+            .fetch_one(&mut tx)
+            .await?;
+        let id: Uuid = row.get("id");
+        let timespan: PgRange<DateTime<Utc>> = row.get("timespan");
+
+        // the window may be free already, entries waiting longer for it still go first
+        sqlx::query("SELECT rsvp.promote_waitlist($1, $2)")
+            .bind(&entry.resource_id)
+            .bind(timespan)
+            .execute(&mut tx)
+            .await?;
+
+        let entry: WaitlistEntry = sqlx::query_as("SELECT * FROM rsvp.waitlist WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(entry)
+    }
+
+    async fn leave_waitlist(&self, id: WaitlistEntryId) -> Result<WaitlistEntry, Error> {
+        let id = Uuid::parse_str(&id).map_err(|_| Error::InvalidWaitlistEntryId(id))?;
+
+        // promoted entries are reservations already, they have to be cancelled instead
+        let entry: WaitlistEntry = sqlx::query_as(
+            "DELETE FROM rsvp.waitlist WHERE id = $1 AND reservation_id IS NULL RETURNING *")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use chrono::DurationRound;
+    use chrono::Datelike;
+    use futures::StreamExt;
+
+    use abi::{CancelRequest, ListenRequest, Policy, Reservation, ReservationStatus, ReservationUpdateType, Resource};
+
+    use crate::{Policies, Resources, Rsvp};
+    use super::*;
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn cancel_should_promote_earliest_waiting_entry() {
+        let manager = setup(&migrated_pool).await;
+        let taken = manager.reserve(new_rsvp("a", 9, 12)).await.unwrap();
+
+        let first = manager.join_waitlist(new_entry("b", 10, 12)).await.unwrap();
+        let second = manager.join_waitlist(new_entry("c", 9, 10)).await.unwrap();
+        let third = manager.join_waitlist(new_entry("d", 11, 13)).await.unwrap();
+        assert_eq!(first.reservation_id, "");
+        assert_eq!(second.reservation_id, "");
+
+        let mut changes = manager.listen(ListenRequest::default()).await.unwrap();
+        manager.cancel(CancelRequest { id: taken.id.clone(), version: taken.version, ..Default::default() })
+            .await
+            .unwrap();
+
+        // b comes first and c still fits next to it, d overlaps b and keeps waiting
+        let mut promoted = vec![];
+        for _ in 0..3 {
+            let change = tokio::time::timeout(Duration::from_secs(5), changes.next())
+                .await
+                .expect("change should arrive")
+                .unwrap()
+                .unwrap();
+            promoted.push((change.op, change.reservation.unwrap()));
+        }
+        assert_eq!(promoted[0].0, ReservationUpdateType::Update as i32);
+        assert_eq!(promoted[0].1.id, taken.id);
+        assert_eq!(promoted[1].0, ReservationUpdateType::Promote as i32);
+        assert_eq!(promoted[1].1.user_id, "b");
+        assert_eq!(promoted[1].1.status, ReservationStatus::Pending as i32);
+        assert_eq!(promoted[1].1.note, "waiting");
+        // promotions are holds, they don't keep the window forever
+        assert!(promoted[1].1.expires_at.is_some());
+        assert_eq!(promoted[2].0, ReservationUpdateType::Promote as i32);
+        assert_eq!(promoted[2].1.user_id, "c");
+
+        let left = manager.leave_waitlist(third.id.clone()).await.unwrap();
+        assert_eq!(left.user_id, "d");
+        assert!(matches!(manager.leave_waitlist(third.id).await, Err(Error::NotFound)));
+        assert!(matches!(manager.leave_waitlist(first.id).await, Err(Error::NotFound)));
+        assert!(matches!(manager.leave_waitlist("nope".to_string()).await, Err(Error::InvalidWaitlistEntryId(id)) if id == "nope"));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn expired_hold_should_promote_waiting_entry() {
+        let manager = setup(&migrated_pool).await;
+        manager.hold(new_rsvp("a", 9, 12), chrono::Duration::milliseconds(300))
+            .await
+            .unwrap();
+        let entry = manager.join_waitlist(new_entry("b", 9, 12)).await.unwrap();
+        assert_eq!(entry.reservation_id, "");
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(manager.expire().await.unwrap().len(), 1);
+
+        let promoted = manager.join_waitlist(new_entry("c", 12, 13)).await.unwrap();
+        // a free window is promoted right away
+        assert_ne!(promoted.reservation_id, "");
+        let rsvp = manager.get(promoted.reservation_id.clone()).await.unwrap();
+        assert_eq!(rsvp.user_id, "c");
+
+        let rsvps = manager.query(abi::ReservationQueryBuilder::default()
+            .user_id("b")
+            .resource_id("hotel-room-1")
+            .build()
+            .unwrap())
+            .await
+            .unwrap();
+        assert_eq!(rsvps.len(), 1);
+        let history = manager.history(rsvps[0].id.clone()).await.unwrap();
+        assert_eq!(history[0].op, ReservationUpdateType::Promote as i32);
+
+        let err = manager.join_waitlist(WaitlistEntry { resource_id: "no-such-room".to_string(), ..entry })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UnknownResource(rid) if rid == "no-such-room"));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn promotion_should_follow_policies_and_expire() {
+        let manager = setup(&migrated_pool).await;
+        let taken = manager.reserve(new_rsvp("a", 9, 12)).await.unwrap();
+        manager.reserve(new_rsvp("b", 14, 15)).await.unwrap();
+        manager.set_policy(Policy { resource_id: "hotel-room-1".to_string(), max_active_reservations: 1, ..Default::default() })
+            .await
+            .unwrap();
+
+        let quota_used = manager.join_waitlist(new_entry("b", 9, 12)).await.unwrap();
+        let short_hold = WaitlistEntry { hold: Some(prost_types::Duration { seconds: 0, nanos: 300_000_000 }), ..new_entry("c", 10, 11) };
+        let waiting = manager.join_waitlist(short_hold).await.unwrap();
+        manager.cancel(CancelRequest { id: taken.id, version: taken.version, ..Default::default() }).await.unwrap();
+
+        // b already has as many reservations as the policy allows, c goes first
+        let entries: Vec<WaitlistEntry> = sqlx::query_as("SELECT * FROM rsvp.waitlist ORDER BY created_at")
+            .fetch_all(&migrated_pool)
+            .await
+            .unwrap();
+        assert_eq!(entries[0].id, quota_used.id);
+        assert_eq!(entries[0].reservation_id, "");
+        assert_eq!(entries[1].id, waiting.id);
+        let promoted = manager.get(entries[1].reservation_id.clone()).await.unwrap();
+        assert_eq!(promoted.user_id, "c");
+
+        // the promotion isn't confirmed in time, it's released like any other hold
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let released = manager.expire().await.unwrap();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].id, promoted.id);
+
+        assert!(matches!(manager.join_waitlist(WaitlistEntry { hold: Some(prost_types::Duration::default()), ..new_entry("d", 9, 10) }).await,
+            Err(Error::InvalidHoldDuration(0))));
+        // less than a microsecond is nothing once stored
+        let hold = Some(prost_types::Duration { seconds: 0, nanos: 500 });
+        assert!(matches!(manager.join_waitlist(WaitlistEntry { hold, ..new_entry("d", 9, 10) }).await,
+            Err(Error::InvalidHoldDuration(0))));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn expire_should_drop_entries_of_passed_windows() {
+        let manager = setup(&migrated_pool).await;
+        let now = chrono::Utc::now();
+        manager.reserve(Reservation::new_pending("a", "hotel-room-1", (now - chrono::Duration::hours(3)).into(), (now + chrono::Duration::hours(1)).into(), ""))
+            .await
+            .unwrap();
+        let entry = |start: chrono::Duration, end: chrono::Duration| WaitlistEntry {
+            start: Some(timestamp(now + start)),
+            end: Some(timestamp(now + end)),
+            ..new_entry("b", 0, 1)
+        };
+        let passed = manager.join_waitlist(entry(chrono::Duration::hours(-3), chrono::Duration::milliseconds(200))).await.unwrap();
+        let current = manager.join_waitlist(entry(chrono::Duration::hours(-1), chrono::Duration::hours(1))).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        manager.expire().await.unwrap();
+        assert!(matches!(manager.leave_waitlist(passed.id).await, Err(Error::NotFound)));
+        manager.leave_waitlist(current.id).await.unwrap();
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn join_should_follow_the_calendar() {
+        let manager = setup(&migrated_pool).await;
+        let hours = |from: i64, to: i64| abi::OpeningHours {
+            weekday: at(0).weekday().number_from_monday() as i32,
+            opens_at: Some(prost_types::Duration { seconds: from * 3600, nanos: 0 }),
+            closes_at: Some(prost_types::Duration { seconds: to * 3600, nanos: 0 }),
+        };
+        let resource = Resource { opening_hours: vec![hours(8, 20)], ..Resource::new("hotel-room-1", "", "hotel-room", 1) };
+        manager.update_resource(resource.clone()).await.unwrap();
+
+        // the entry could never be promoted
+        assert!(matches!(manager.join_waitlist(new_entry("a", 19, 21)).await, Err(Error::OutsideOpeningHours(_))));
+        manager.join_waitlist(new_entry("a", 9, 12)).await.unwrap();
+
+        let holiday = Resource { holidays: vec![at(0).format("%Y-%m-%d").to_string()], ..resource };
+        manager.update_resource(holiday).await.unwrap();
+        assert!(matches!(manager.join_waitlist(new_entry("b", 9, 12)).await, Err(Error::OutsideOpeningHours(_))));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn join_should_snap_to_slots() {
+        let manager = setup(&migrated_pool).await;
+        let hourly = Resource {
+            slot: Some(prost_types::Duration { seconds: 3600, nanos: 0 }),
+            ..Resource::new("hotel-room-1", "", "hotel-room", 1)
+        };
+        manager.update_resource(hourly.clone()).await.unwrap();
+
+        // the entry could never be promoted
+        let half_past = WaitlistEntry { start: Some(timestamp(at(9) + chrono::Duration::minutes(30))), ..new_entry("a", 9, 12) };
+        assert!(matches!(manager.join_waitlist(half_past.clone()).await, Err(Error::InvalidSlot(_))));
+
+        // it waits for the window it would be promoted to
+        manager.update_resource(Resource { rounding: abi::SlotRounding::Outward as i32, ..hourly }).await.unwrap();
+        let taken = manager.reserve(new_rsvp("b", 9, 12)).await.unwrap();
+        let entry = manager.join_waitlist(half_past).await.unwrap();
+        assert_eq!(entry.start, Some(timestamp(at(9))));
+        assert_eq!(entry.reservation_id, "");
+
+        manager.cancel(CancelRequest { id: taken.id, version: taken.version, ..Default::default() }).await.unwrap();
+        let entry: WaitlistEntry = sqlx::query_as("SELECT * FROM rsvp.waitlist WHERE id = $1")
+            .bind(Uuid::parse_str(&entry.id).unwrap())
+            .fetch_one(&migrated_pool)
+            .await
+            .unwrap();
+        let promoted = manager.get(entry.reservation_id).await.unwrap();
+        assert_eq!(promoted.start, Some(timestamp(at(9))));
+        assert_eq!(promoted.end, Some(timestamp(at(12))));
+    }
+
+    async fn setup(pool: &sqlx::PgPool) -> ReservationManager {
+        let manager = ReservationManager::new(pool.clone());
+        manager.create_resource(Resource::new("hotel-room-1", "", "hotel-room", 1)).await.unwrap();
+        manager
+    }
+
+    /// tomorrow at hour h
+    fn at(h: i64) -> DateTime<Utc> {
+        (Utc::now() + chrono::Duration::days(1)).duration_trunc(chrono::Duration::days(1)).unwrap() + chrono::Duration::hours(h)
+    }
+
+    fn timestamp(dt: DateTime<Utc>) -> prost_types::Timestamp {
+        prost_types::Timestamp { seconds: dt.timestamp(), nanos: dt.timestamp_subsec_nanos() as _ }
+    }
+
+    fn new_rsvp(uid: &str, start: i64, end: i64) -> Reservation {
+        Reservation::new_pending(uid, "hotel-room-1", at(start).into(), at(end).into(), "")
+    }
+
+    fn new_entry(uid: &str, start: i64, end: i64) -> WaitlistEntry {
+        WaitlistEntry {
+            user_id: uid.to_string(),
+            resource_id: "hotel-room-1".to_string(),
+            start: Some(timestamp(at(start))),
+            end: Some(timestamp(at(end))),
+            note: "waiting".to_string(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use chrono::{DateTime, Duration, Utc};
+use hyper::header::CONTENT_TYPE;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use tonic::{Code, Status};
+
+use abi::{convert_to_timestamp, ReservationQuery, ReservationStatus};
+use reservation::{ReservationManager, Rsvp};
+
+/// path of the iCalendar feed, the reservation query is taken from the url query string
+pub const CALENDAR_PATH: &str = "/calendar.ics";
+/// the longest time range a feed covers
+pub const MAX_FEED_DAYS: i64 = 366;
+// how far back a feed without start goes
+const DEFAULT_PAST_DAYS: i64 = 30;
+
+/// serve the reservations matching a query as an iCalendar feed, e.g.
+/// GET /calendar.ics?resource_id=room-1&status=confirmed&start=2023-01-01T00:00:00Z.
+/// The feed has no authentication, notes and user ids are only included if asked for with notes=true and users=true
+pub async fn serve_calendar(manager: ReservationManager, addr: SocketAddr) -> Result<(), hyper::Error> {
+    let make_svc = make_service_fn(move |_| {
+        let manager = manager.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let manager = manager.clone();
+                async move { Ok::<_, Infallible>(handle(&manager, req).await) }
+            }))
+        }
+    });
+
+    Server::bind(&addr).serve(make_svc).await
+}
+
+async fn handle(manager: &ReservationManager, req: Request<Body>) -> Response<Body> {
+    if req.uri().path() != CALENDAR_PATH {
+        return error(StatusCode::NOT_FOUND, "not found");
+    }
+    if req.method() != Method::GET {
+        return error(StatusCode::METHOD_NOT_ALLOWED, "only GET is allowed");
+    }
+
+    let now = Utc::now();
+    let (query, feed) = match parse_query(req.uri().query().unwrap_or_default(), now) {
+        Ok(parsed) => parsed,
+        Err(msg) => return error(StatusCode::BAD_REQUEST, &msg),
+    };
+    let name = match (query.resource_id.as_str(), query.user_id.as_str()) {
+        (rid, "") => rid.to_string(),
+        ("", uid) => uid.to_string(),
+        (rid, uid) => format!("{} of {}", rid, uid),
+    };
+
+    let ics = manager.query(query)
+        .await
+        .and_then(|mut rsvps| {
+            for rsvp in rsvps.iter_mut() {
+                if !feed.notes {
+                    rsvp.note.clear();
+                }
+                if !feed.users {
+                    rsvp.user_id.clear();
+                }
+            }
+            abi::to_icalendar(&name, &rsvps, now)
+        });
+    match ics {
+        Ok(ics) => Response::builder()
+            .header(CONTENT_TYPE, "text/calendar; charset=utf-8")
+            .body(Body::from(ics))
+            .unwrap(),
+        Err(e) => {
+            let status = Status::from(e);
+            error(http_status(status.code()), status.message())
+        }
+    }
+}
+
+/// what a feed exports besides the time and place of reservations
+#[derive(Default)]
+struct FeedFields {
+    notes: bool,
+    users: bool,
+}
+
+/// parse the query string into a reservation query and the fields exported, statuses are given
+/// in lowercase (e.g. confirmed) and times in RFC 3339. A resource or user has to be given, the time range
+/// defaults to MAX_FEED_DAYS from DEFAULT_PAST_DAYS before `now` and can't be longer
+fn parse_query(qs: &str, now: DateTime<Utc>) -> Result<(ReservationQuery, FeedFields), String> {
+    let mut query = ReservationQuery::default();
+    let (mut start, mut end, mut feed) = (None, None, FeedFields::default());
+    for (key, value) in form_urlencoded::parse(qs.as_bytes()) {
+        match key.as_ref() {
+            "resource_id" => query.resource_id = value.into_owned(),
+            "user_id" => query.user_id = value.into_owned(),
+            "status" => {
+                let status = ReservationStatus::from_str_name(&format!("RESERVATION_STATUS_{}", value.to_uppercase()))
+                    .ok_or_else(|| format!("invalid status: {}", value))?;
+                query.status = status as i32;
+            }
+            "start" => start = Some(parse_time(&value)?),
+            "end" => end = Some(parse_time(&value)?),
+            "desc" => query.desc = value.parse().map_err(|_| format!("invalid desc: {}", value))?,
+            "notes" => feed.notes = value.parse().map_err(|_| format!("invalid notes: {}", value))?,
+            "users" => feed.users = value.parse().map_err(|_| format!("invalid users: {}", value))?,
+            _ => return Err(format!("unknown parameter: {}", key)),
+        }
+    }
+
+    if query.resource_id.is_empty() && query.user_id.is_empty() {
+        return Err("resource_id or user_id is required".to_string());
+    }
+    let start = start.unwrap_or_else(|| now - Duration::days(DEFAULT_PAST_DAYS));
+    let end = end.unwrap_or_else(|| start + Duration::days(MAX_FEED_DAYS));
+    if end - start > Duration::days(MAX_FEED_DAYS) {
+        return Err(format!("a feed covers at most {} days", MAX_FEED_DAYS));
+    }
+    query.start = Some(convert_to_timestamp(&start));
+    query.end = Some(convert_to_timestamp(&end));
+
+    Ok((query, feed))
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    value.parse().map_err(|_| format!("invalid time: {}", value))
+}
+
+fn http_status(code: Code) -> StatusCode {
+    match code {
+        Code::InvalidArgument | Code::OutOfRange | Code::FailedPrecondition => StatusCode::BAD_REQUEST,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn error(status: StatusCode, msg: &str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Body::from(msg.to_string()))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use abi::{ConfirmRequest, Reservation, Resource};
+    use reservation::Resources;
+
+    use super::*;
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn calendar_should_export_reservations_of_query() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager.create_resource(Resource::new("ocean-view-room-713", "Ocean view", "room", 1)).await.unwrap();
+        let pending = manager.reserve(Reservation::new_pending("gyg", "ocean-view-room-713",
+                                                               "2022-12-25T15:00:00-0700".parse().unwrap(),
+                                                               "2022-12-28T12:00:00-0700".parse().unwrap(),
+                                                               "late check-in"))
+            .await
+            .unwrap();
+        let confirmed = manager.reserve(Reservation::new_pending("gyg", "ocean-view-room-713",
+                                                                 "2023-01-10T15:00:00-0700".parse().unwrap(),
+                                                                 "2023-01-12T12:00:00-0700".parse().unwrap(),
+                                                                 "confirmed"))
+            .await
+            .unwrap();
+        let confirmed = manager.change_status(ConfirmRequest { id: confirmed.id, version: confirmed.version }).await.unwrap();
+
+        let resp = get(&manager, "/calendar.ics?resource_id=ocean-view-room-713&start=2022-12-01T00:00:00Z").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[CONTENT_TYPE], "text/calendar; charset=utf-8");
+        let ics = body(resp).await;
+        assert!(ics.contains(&format!("UID:{}\r\n", pending.id)));
+        assert!(ics.contains(&format!("UID:{}\r\n", confirmed.id)));
+        assert!(ics.contains("STATUS:TENTATIVE\r\n"));
+        assert!(ics.contains("X-WR-CALNAME:ocean-view-room-713\r\n"));
+        // notes and user ids stay private unless asked for
+        assert!(!ics.contains("DESCRIPTION:"));
+        assert!(ics.contains("SUMMARY:ocean-view-room-713\r\n"));
+        assert!(!ics.contains("gyg"));
+        let ics = body(get(&manager, "/calendar.ics?user_id=gyg&start=2022-12-01T00:00:00Z&notes=true&users=true").await).await;
+        assert!(ics.contains("DESCRIPTION:late check-in\r\n"));
+        assert!(ics.contains("SUMMARY:ocean-view-room-713 by gyg\r\n"));
+
+        // the feed starts a while before now by default
+        let ics = body(get(&manager, "/calendar.ics?resource_id=ocean-view-room-713").await).await;
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 0);
+
+        let ics = body(get(&manager, "/calendar.ics?user_id=gyg&status=confirmed&start=2023-01-01T00:00:00Z").await).await;
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains(&format!("UID:{}\r\nDTSTAMP:", confirmed.id)));
+        assert!(ics.contains("DTSTART:20230110T220000Z\r\nDTEND:20230112T190000Z\r\n"));
+        assert!(ics.contains("STATUS:CONFIRMED\r\n"));
+
+        // every reservation of every resource isn't a feed
+        assert_eq!(get(&manager, "/calendar.ics").await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(get(&manager, "/calendar.ics?user_id=gyg&start=2022-01-01T00:00:00Z&end=2023-06-01T00:00:00Z").await.status(),
+                   StatusCode::BAD_REQUEST);
+        assert_eq!(get(&manager, "/calendar.ics?user_id=gyg&start=nope").await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(get(&manager, "/calendar.ics?user_id=gyg&status=maybe").await.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(get(&manager, "/calendar.ics?user_id=gyg&start=2023-01-02T00:00:00Z&end=2023-01-01T00:00:00Z").await.status(),
+                   StatusCode::BAD_REQUEST);
+        assert_eq!(get(&manager, "/reservations").await.status(), StatusCode::NOT_FOUND);
+    }
+
+    async fn get(manager: &ReservationManager, uri: &str) -> Response<Body> {
+        handle(manager, Request::get(uri).body(Body::empty()).unwrap()).await
+    }
+
+    async fn body(resp: Response<Body>) -> String {
+        let bytes = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+}
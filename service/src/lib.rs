@@ -0,0 +1,65 @@
+mod calendar;
+mod reaper;
+mod service;
+
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures::Stream;
+use tonic::transport::Server;
+use tonic::Status;
+use tracing::{error, info};
+
+use abi::reservation_service_server::ReservationServiceServer;
+use abi::{Config, ListenResponse, Reservation};
+use reservation::ReservationManager;
+
+pub use calendar::{serve_calendar, CALENDAR_PATH};
+pub use reaper::reap_expired_holds;
+pub use service::ACTOR_KEY;
+
+/// server streaming response of query
+pub type ReservationStream = Pin<Box<dyn Stream<Item = Result<Reservation, Status>> + Send>>;
+/// server streaming response of listen
+pub type ListenResponseStream = Pin<Box<dyn Stream<Item = Result<ListenResponse, Status>> + Send>>;
+
+pub struct RsvpService {
+    manager: ReservationManager,
+}
+
+impl RsvpService {
+    pub fn new(manager: ReservationManager) -> Self {
+        Self { manager }
+    }
+
+    pub async fn from_config(config: &Config) -> Result<Self, abi::Error> {
+        Ok(Self::new(ReservationManager::from_config(&config.db).await?))
+    }
+}
+
+/// start the grpc server on the address given by config, along with the calendar http server if configured
+pub async fn start_server(config: &Config) -> anyhow::Result<()> {
+    let addr = config.server.url().parse()?;
+
+    let svc = RsvpService::from_config(config).await?;
+    tokio::spawn(reap_expired_holds(
+        svc.manager.with_actor("reaper")?,
+        Duration::from_secs(config.server.reap_interval),
+    ));
+    if let Some(url) = config.server.calendar_url() {
+        let addr = url.parse()?;
+        let manager = svc.manager.clone();
+        info!("serving calendar on http://{}{}", addr, CALENDAR_PATH);
+        tokio::spawn(async move {
+            if let Err(e) = serve_calendar(manager, addr).await {
+                error!("calendar server failed: {}", e);
+            }
+        });
+    }
+    let svc = ReservationServiceServer::new(svc);
+
+    info!("listening on {}", addr);
+    Server::builder().add_service(svc).serve(addr).await?;
+
+    Ok(())
+}
@@ -1,3 +1,16 @@
-fn main() {
-    println!("Hello, world!");
+use std::env;
+
+use abi::Config;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    // config file path, could be overridden by RESERVATION_CONFIG
+    let filename = env::var("RESERVATION_CONFIG")
+        .unwrap_or_else(|_| "reservation.yml".to_string());
+
+    let config = Config::load(filename)?;
+
+    service::start_server(&config).await
 }
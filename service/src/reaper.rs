@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use reservation::{ReservationManager, Rsvp};
+use tracing::{error, info};
+
+/// release the holds not confirmed in time every interval, each released hold
+/// shows up in the change feed as an EXPIRE change
+pub async fn reap_expired_holds(manager: ReservationManager, interval: Duration) {
+    // tokio panics on a zero interval
+    let mut ticker = tokio::time::interval(interval.max(Duration::from_secs(1)));
+    loop {
+        ticker.tick().await;
+        match manager.expire().await {
+            Ok(released) if !released.is_empty() => info!("released {} expired holds", released.len()),
+            Ok(_) => {}
+            Err(e) => error!("failed to release expired holds: {}", e),
+        }
+    }
+}
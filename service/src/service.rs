@@ -0,0 +1,733 @@
+use futures::{stream, TryStreamExt};
+use tonic::{Request, Response, Status};
+
+use abi::reservation_service_server::ReservationService;
+use abi::{
+    CancelRequest, CancelResponse, ConfirmRequest, ConfirmResponse, CreateResourceRequest,
+    CreateResourceResponse, FilterRequest, FilterResponse, GetRequest, GetResourceRequest,
+    GetResourceResponse, GetResponse, ListenRequest, QueryRequest, ReserveRequest,
+    ReserveResponse, UpdateRequest, UpdateResourceRequest, UpdateResourceResponse,
+    UpdateResponse,
+};
+use abi::{
+    CancelSeriesRequest, CancelSeriesResponse, GetSeriesRequest, GetSeriesResponse,
+    ReserveSeriesRequest, ReserveSeriesResponse, UpdateSeriesRequest, UpdateSeriesResponse,
+};
+use abi::{
+    AvailabilityRequest, AvailabilityResponse, BlockRequest, BlockResponse, HistoryRequest,
+    HistoryResponse, RescheduleRequest, RescheduleResponse, ReservationStatus, ReserveAnyRequest,
+    ReserveAnyResponse, TransitionRequest, TransitionResponse, UnblockRequest, UnblockResponse,
+};
+use abi::{
+    CancelBundleRequest, CancelBundleResponse, ConfirmBundleRequest, ConfirmBundleResponse,
+    ReserveBundleRequest, ReserveBundleResponse,
+};
+use abi::{
+    GetPolicyRequest, GetPolicyResponse, JoinWaitlistRequest, JoinWaitlistResponse,
+    LeaveWaitlistRequest, LeaveWaitlistResponse, SetPolicyRequest, SetPolicyResponse,
+};
+use reservation::{Bundles, Policies, ReservationManager, Resources, Rsvp, Series, Waitlist};
+
+use crate::{ListenResponseStream, ReservationStream, RsvpService};
+
+/// metadata key of the user making the request, recorded in the history of changed reservations
+pub const ACTOR_KEY: &str = "x-actor";
+
+#[tonic::async_trait]
+impl ReservationService for RsvpService {
+    async fn reserve(
+        &self,
+        request: Request<ReserveRequest>,
+    ) -> Result<Response<ReserveResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let request = request.into_inner();
+        if request.reservation.is_none() {
+            return Err(Status::invalid_argument("missing reservation"));
+        }
+
+        let reservation = manager.make_reservation(request).await?;
+
+        Ok(Response::new(ReserveResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    async fn reserve_any(
+        &self,
+        request: Request<ReserveAnyRequest>,
+    ) -> Result<Response<ReserveAnyResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let request = request.into_inner();
+        if request.reservation.is_none() {
+            return Err(Status::invalid_argument("missing reservation"));
+        }
+
+        let reservation = manager.reserve_any(request).await?;
+
+        Ok(Response::new(ReserveAnyResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    async fn confirm(
+        &self,
+        request: Request<ConfirmRequest>,
+    ) -> Result<Response<ConfirmResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let reservation = manager.change_status(request.into_inner()).await?;
+
+        Ok(Response::new(ConfirmResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    async fn transition(
+        &self,
+        request: Request<TransitionRequest>,
+    ) -> Result<Response<TransitionResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let request = request.into_inner();
+        let status = ReservationStatus::from_i32(request.status)
+            .ok_or_else(|| Status::invalid_argument("unknown status"))?;
+
+        let reservation = manager.transition(request.id, status, request.version).await?;
+
+        Ok(Response::new(TransitionResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    async fn update(
+        &self,
+        request: Request<UpdateRequest>,
+    ) -> Result<Response<UpdateResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let reservation = manager.update_note(request.into_inner()).await?;
+
+        Ok(Response::new(UpdateResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    async fn cancel(
+        &self,
+        request: Request<CancelRequest>,
+    ) -> Result<Response<CancelResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let reservation = manager.cancel(request.into_inner()).await?;
+
+        Ok(Response::new(CancelResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    async fn reschedule(
+        &self,
+        request: Request<RescheduleRequest>,
+    ) -> Result<Response<RescheduleResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let reservation = manager.reschedule(request.into_inner()).await?;
+
+        Ok(Response::new(RescheduleResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    async fn get(&self, request: Request<GetRequest>) -> Result<Response<GetResponse>, Status> {
+        let request = request.into_inner();
+        let reservation = self.manager.get(request.id).await?;
+
+        Ok(Response::new(GetResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    type queryStream = ReservationStream;
+
+    async fn query(
+        &self,
+        request: Request<QueryRequest>,
+    ) -> Result<Response<Self::queryStream>, Status> {
+        let request = request.into_inner();
+        let query = request
+            .query
+            .ok_or_else(|| Status::invalid_argument("missing query"))?;
+
+        let reservations = self.manager.query(query).await?;
+
+        Ok(Response::new(Box::pin(stream::iter(
+            reservations.into_iter().map(Ok),
+        ))))
+    }
+
+    async fn filter(
+        &self,
+        request: Request<FilterRequest>,
+    ) -> Result<Response<FilterResponse>, Status> {
+        let request = request.into_inner();
+        let filter = request
+            .filter
+            .ok_or_else(|| Status::invalid_argument("missing filter"))?;
+
+        let (pager, reservations) = self.manager.filter(filter).await?;
+
+        Ok(Response::new(FilterResponse {
+            reservations,
+            pager: Some(pager),
+        }))
+    }
+
+    type listenStream = ListenResponseStream;
+
+    async fn listen(
+        &self,
+        request: Request<ListenRequest>,
+    ) -> Result<Response<Self::listenStream>, Status> {
+        let changes = self.manager.listen(request.into_inner()).await?;
+
+        Ok(Response::new(Box::pin(changes.map_err(Status::from))))
+    }
+
+    async fn create_resource(
+        &self,
+        request: Request<CreateResourceRequest>,
+    ) -> Result<Response<CreateResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = request
+            .resource
+            .ok_or_else(|| Status::invalid_argument("missing resource"))?;
+
+        let resource = self.manager.create_resource(resource).await?;
+
+        Ok(Response::new(CreateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    async fn update_resource(
+        &self,
+        request: Request<UpdateResourceRequest>,
+    ) -> Result<Response<UpdateResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = request
+            .resource
+            .ok_or_else(|| Status::invalid_argument("missing resource"))?;
+
+        let resource = self.manager.update_resource(resource).await?;
+
+        Ok(Response::new(UpdateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    async fn get_resource(
+        &self,
+        request: Request<GetResourceRequest>,
+    ) -> Result<Response<GetResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = self.manager.get_resource(request.id).await?;
+
+        Ok(Response::new(GetResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    async fn block(
+        &self,
+        request: Request<BlockRequest>,
+    ) -> Result<Response<BlockResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let (block, released) = manager.block(request.into_inner()).await?;
+
+        Ok(Response::new(BlockResponse {
+            block: Some(block),
+            released,
+        }))
+    }
+
+    async fn unblock(
+        &self,
+        request: Request<UnblockRequest>,
+    ) -> Result<Response<UnblockResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let request = request.into_inner();
+        let block = manager.unblock(request.id).await?;
+
+        Ok(Response::new(UnblockResponse { block: Some(block) }))
+    }
+
+    async fn reserve_series(
+        &self,
+        request: Request<ReserveSeriesRequest>,
+    ) -> Result<Response<ReserveSeriesResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let request = request.into_inner();
+        let series = request
+            .series
+            .ok_or_else(|| Status::invalid_argument("missing series"))?;
+
+        let (series, reservations) = manager.reserve_series(series).await?;
+
+        Ok(Response::new(ReserveSeriesResponse {
+            series: Some(series),
+            reservations,
+        }))
+    }
+
+    async fn update_series(
+        &self,
+        request: Request<UpdateSeriesRequest>,
+    ) -> Result<Response<UpdateSeriesResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let request = request.into_inner();
+        if request.series.is_none() {
+            return Err(Status::invalid_argument("missing series"));
+        }
+
+        let (series, reservations) = manager.update_series(request).await?;
+
+        Ok(Response::new(UpdateSeriesResponse {
+            series: Some(series),
+            reservations,
+        }))
+    }
+
+    async fn cancel_series(
+        &self,
+        request: Request<CancelSeriesRequest>,
+    ) -> Result<Response<CancelSeriesResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let reservations = manager.cancel_series(request.into_inner()).await?;
+
+        Ok(Response::new(CancelSeriesResponse { reservations }))
+    }
+
+    async fn get_series(
+        &self,
+        request: Request<GetSeriesRequest>,
+    ) -> Result<Response<GetSeriesResponse>, Status> {
+        let request = request.into_inner();
+        let series = self.manager.get_series(request.id).await?;
+
+        Ok(Response::new(GetSeriesResponse {
+            series: Some(series),
+        }))
+    }
+
+    async fn reserve_bundle(
+        &self,
+        request: Request<ReserveBundleRequest>,
+    ) -> Result<Response<ReserveBundleResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let reservations = manager.reserve_bundle(request.into_inner().reservations).await?;
+
+        Ok(Response::new(ReserveBundleResponse {
+            bundle_id: reservations[0].bundle_id.clone(),
+            reservations,
+        }))
+    }
+
+    async fn confirm_bundle(
+        &self,
+        request: Request<ConfirmBundleRequest>,
+    ) -> Result<Response<ConfirmBundleResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let reservations = manager.confirm_bundle(request.into_inner().bundle_id).await?;
+
+        Ok(Response::new(ConfirmBundleResponse { reservations }))
+    }
+
+    async fn cancel_bundle(
+        &self,
+        request: Request<CancelBundleRequest>,
+    ) -> Result<Response<CancelBundleResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let reservations = manager.cancel_bundle(request.into_inner()).await?;
+
+        Ok(Response::new(CancelBundleResponse { reservations }))
+    }
+
+    async fn availability(
+        &self,
+        request: Request<AvailabilityRequest>,
+    ) -> Result<Response<AvailabilityResponse>, Status> {
+        let slots = self.manager.availability(request.into_inner()).await?;
+
+        Ok(Response::new(AvailabilityResponse { slots }))
+    }
+
+    async fn history(
+        &self,
+        request: Request<HistoryRequest>,
+    ) -> Result<Response<HistoryResponse>, Status> {
+        let request = request.into_inner();
+        let entries = self.manager.history(request.id).await?;
+
+        Ok(Response::new(HistoryResponse { entries }))
+    }
+
+    async fn join_waitlist(
+        &self,
+        request: Request<JoinWaitlistRequest>,
+    ) -> Result<Response<JoinWaitlistResponse>, Status> {
+        let manager = self.manager_for(&request)?;
+        let entry = request
+            .into_inner()
+            .entry
+            .ok_or_else(|| Status::invalid_argument("missing entry"))?;
+        let entry = manager.join_waitlist(entry).await?;
+
+        Ok(Response::new(JoinWaitlistResponse { entry: Some(entry) }))
+    }
+
+    async fn leave_waitlist(
+        &self,
+        request: Request<LeaveWaitlistRequest>,
+    ) -> Result<Response<LeaveWaitlistResponse>, Status> {
+        let entry = self.manager.leave_waitlist(request.into_inner().id).await?;
+
+        Ok(Response::new(LeaveWaitlistResponse { entry: Some(entry) }))
+    }
+
+    async fn set_policy(
+        &self,
+        request: Request<SetPolicyRequest>,
+    ) -> Result<Response<SetPolicyResponse>, Status> {
+        let policy = request
+            .into_inner()
+            .policy
+            .ok_or_else(|| Status::invalid_argument("missing policy"))?;
+        let policy = self.manager.set_policy(policy).await?;
+
+        Ok(Response::new(SetPolicyResponse {
+            policy: Some(policy),
+        }))
+    }
+
+    async fn get_policy(
+        &self,
+        request: Request<GetPolicyRequest>,
+    ) -> Result<Response<GetPolicyResponse>, Status> {
+        let request = request.into_inner();
+        let policy = self.manager.get_policy(request.resource_id).await?;
+
+        Ok(Response::new(GetPolicyResponse {
+            policy: Some(policy),
+        }))
+    }
+}
+
+impl RsvpService {
+    /// the manager to make changes with, recording the actor given in request metadata.
+    /// fail with InvalidActor if the actor is too long to be recorded
+    fn manager_for<T>(&self, request: &Request<T>) -> Result<ReservationManager, abi::Error> {
+        match request.metadata().get(ACTOR_KEY).and_then(|v| v.to_str().ok()) {
+            Some(actor) if !actor.is_empty() => self.manager.with_actor(actor),
+            _ => Ok(self.manager.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use abi::{
+        BundleConflictDetails, Reservation, ReservationConflictDetails, ReservationSeries, ReservationUpdateType,
+        Resource, SeriesConflictDetails,
+    };
+    use reservation::ReservationManager;
+
+    use super::*;
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn rpc_reserve_should_work() {
+        let service = setup(&migrated_pool).await;
+        let rsvp = Reservation::new_pending("gyg",
+                                            "ocean-view-room-713",
+                                            "2022-12-25T15:00:00-0700".parse().unwrap(),
+                                            "2022-12-28T12:00:00-0700".parse().unwrap(),
+                                            "rpc");
+
+        let ret = service.reserve(Request::new(ReserveRequest { reservation: Some(rsvp), ..Default::default() }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_ne!(ret.reservation.unwrap().id, "")
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn rpc_reserve_without_reservation_should_be_rejected() {
+        let service = setup(&migrated_pool).await;
+
+        let status = service.reserve(Request::new(ReserveRequest { reservation: None, ..Default::default() }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::InvalidArgument)
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn rpc_reserve_conflict_should_return_details() {
+        let service = setup(&migrated_pool).await;
+        let rsvp = Reservation::new_pending("gyg",
+                                            "ocean-view-room-713",
+                                            "2022-12-25T15:00:00-0700".parse().unwrap(),
+                                            "2022-12-28T12:00:00-0700".parse().unwrap(),
+                                            "rpc");
+        let first = service.reserve(Request::new(ReserveRequest { reservation: Some(rsvp.clone()), ..Default::default() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservation
+            .unwrap();
+
+        let status = service.reserve(Request::new(ReserveRequest { reservation: Some(rsvp), ..Default::default() }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::AlreadyExists);
+
+        let details = ReservationConflictDetails::from_status(&status).unwrap();
+        let old = details.old.unwrap();
+        assert_eq!(old.reservation_id, first.id);
+        assert_eq!(old.resource_id, "ocean-view-room-713");
+        assert_eq!(old.start, first.start);
+        assert_eq!(old.end, first.end);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn rpc_reserve_series_conflict_should_return_every_conflict() {
+        let service = setup(&migrated_pool).await;
+        let rsvp = Reservation::new_pending("gyg",
+                                            "ocean-view-room-713",
+                                            "2023-01-10T10:30:00Z".parse().unwrap(),
+                                            "2023-01-10T11:30:00Z".parse().unwrap(),
+                                            "rpc");
+        let first = service.reserve(Request::new(ReserveRequest { reservation: Some(rsvp), ..Default::default() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservation
+            .unwrap();
+
+        let series = ReservationSeries::new_pending("gyg",
+                                                    "ocean-view-room-713",
+                                                    "2023-01-03T10:00:00Z".parse().unwrap(),
+                                                    "2023-01-03T11:00:00Z".parse().unwrap(),
+                                                    "FREQ=WEEKLY;COUNT=3",
+                                                    "rpc");
+        let status = service.reserve_series(Request::new(ReserveSeriesRequest { series: Some(series) }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::AlreadyExists);
+
+        let details = SeriesConflictDetails::from_status(&status).unwrap();
+        assert_eq!(details.conflicts.len(), 1);
+        let conflict = &details.conflicts[0];
+        assert_eq!(conflict.old.as_ref().unwrap().reservation_id, first.id);
+        assert_eq!(conflict.new.as_ref().unwrap().start, Some("2023-01-10T10:00:00Z".parse().unwrap()));
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn rpc_reserve_with_hold_should_expire() {
+        let service = setup(&migrated_pool).await;
+        let rsvp = Reservation::new_pending("gyg",
+                                            "ocean-view-room-713",
+                                            "2022-12-25T15:00:00-0700".parse().unwrap(),
+                                            "2022-12-28T12:00:00-0700".parse().unwrap(),
+                                            "rpc");
+        let hold = Some(prost_types::Duration { seconds: 0, nanos: 300_000_000 });
+
+        let ret = service.reserve(Request::new(ReserveRequest { reservation: Some(rsvp.clone()), hold, ..Default::default() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservation
+            .unwrap();
+        assert!(ret.expires_at.is_some());
+
+        // the reaper ticks right away and then every second, the hold is released by the second tick
+        let reaper = tokio::spawn(crate::reap_expired_holds(ReservationManager::new(migrated_pool.clone()), Duration::from_secs(1)));
+        let mut found = true;
+        for _ in 0..30 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            // released holds are removed, only their history is left
+            found = service.get(Request::new(GetRequest { id: ret.id.clone() })).await.is_ok();
+            if !found {
+                break;
+            }
+        }
+        reaper.abort();
+        assert!(!found);
+
+        let history = service.history(Request::new(HistoryRequest { id: ret.id.clone() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .entries;
+        assert_eq!(history.last().unwrap().op, ReservationUpdateType::Expire as i32);
+
+        // the slot is free again
+        service.reserve(Request::new(ReserveRequest { reservation: Some(rsvp.clone()), ..Default::default() }))
+            .await
+            .unwrap();
+
+        // postgres keeps the hold to the microsecond
+        let later = Reservation::new_pending("gyg",
+                                             "ocean-view-room-713",
+                                             "2023-01-25T15:00:00-0700".parse().unwrap(),
+                                             "2023-01-28T12:00:00-0700".parse().unwrap(),
+                                             "rpc");
+        let hold = Some(prost_types::Duration { seconds: 900, nanos: 1_001 });
+        let ret = service.reserve(Request::new(ReserveRequest { reservation: Some(later), hold, ..Default::default() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservation
+            .unwrap();
+        assert!(ret.expires_at.is_some());
+
+        for (seconds, nanos) in [(-1, 0), (0, 500), (i64::MAX, 0)] {
+            let hold = Some(prost_types::Duration { seconds, nanos });
+            let status = service.reserve(Request::new(ReserveRequest { reservation: Some(rsvp.clone()), hold, ..Default::default() }))
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        }
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn rpc_history_should_record_actor_from_metadata() {
+        let service = setup(&migrated_pool).await;
+        let rsvp = Reservation::new_pending("gyg",
+                                            "ocean-view-room-713",
+                                            "2022-12-25T15:00:00-0700".parse().unwrap(),
+                                            "2022-12-28T12:00:00-0700".parse().unwrap(),
+                                            "rpc");
+        let mut request = Request::new(ReserveRequest { reservation: Some(rsvp), ..Default::default() });
+        request.metadata_mut().insert(ACTOR_KEY, "front-desk".parse().unwrap());
+        let rsvp = service.reserve(request)
+            .await
+            .unwrap()
+            .into_inner()
+            .reservation
+            .unwrap();
+
+        let ret = service.history(Request::new(HistoryRequest { id: rsvp.id.clone() }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(ret.entries.len(), 1);
+        assert_eq!(ret.entries[0].actor, "front-desk");
+        assert_eq!(ret.entries[0].after, Some(rsvp.clone()));
+
+        // an actor too long to be recorded is refused before anything is written
+        let mut request = Request::new(UpdateRequest { id: rsvp.id, note: "late".to_string(), version: rsvp.version });
+        request.metadata_mut().insert(ACTOR_KEY, "a".repeat(65).parse().unwrap());
+        let status = service.update(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn rpc_bundle_should_confirm_and_cancel_together() {
+        let service = setup(&migrated_pool).await;
+        let bundle = ReserveBundleRequest { reservations: two_meetings() };
+        let ret = service.reserve_bundle(Request::new(bundle))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(ret.reservations.len(), 2);
+        assert!(ret.reservations.iter().all(|r| r.bundle_id == ret.bundle_id));
+
+        let confirmed = service.confirm_bundle(Request::new(ConfirmBundleRequest { bundle_id: ret.bundle_id.clone() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservations;
+        assert_eq!(confirmed.len(), 2);
+
+        let request = CancelBundleRequest { bundle_id: ret.bundle_id.clone(), ..Default::default() };
+        let cancelled = service.cancel_bundle(Request::new(request.clone()))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservations;
+        assert_eq!(cancelled.len(), 2);
+
+        // nothing is left to cancel
+        let status = service.cancel_bundle(Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[sqlx_database_tester::test(
+    pool(variable = "migrated_pool", migrations = "../migrations")
+    )]
+    async fn rpc_bundle_errors_should_return_status() {
+        let service = setup(&migrated_pool).await;
+        let first = service.reserve(Request::new(ReserveRequest { reservation: Some(two_meetings().remove(1)), ..Default::default() }))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservation
+            .unwrap();
+
+        let status = service.reserve_bundle(Request::new(ReserveBundleRequest { reservations: two_meetings() }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::AlreadyExists);
+        let details = BundleConflictDetails::from_status(&status).unwrap();
+        assert_eq!(details.conflicts.len(), 1);
+        assert_eq!(details.conflicts[0].old.as_ref().unwrap().reservation_id, first.id);
+
+        let status = service.reserve_bundle(Request::new(ReserveBundleRequest { reservations: vec![] }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let unknown = "00000000-0000-0000-0000-000000000000".to_string();
+        let status = service.confirm_bundle(Request::new(ConfirmBundleRequest { bundle_id: unknown.clone() }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        let status = service.cancel_bundle(Request::new(CancelBundleRequest { bundle_id: unknown, ..Default::default() }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        let status = service.cancel_bundle(Request::new(CancelBundleRequest { bundle_id: "bundle".to_string(), ..Default::default() }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    /// two meetings back to back in the same room
+    fn two_meetings() -> Vec<Reservation> {
+        vec![
+            Reservation::new_pending("gyg", "ocean-view-room-713", "2023-01-03T10:00:00Z".parse().unwrap(), "2023-01-03T11:00:00Z".parse().unwrap(), "rpc"),
+            Reservation::new_pending("gyg", "ocean-view-room-713", "2023-01-03T11:00:00Z".parse().unwrap(), "2023-01-03T12:00:00Z".parse().unwrap(), "rpc"),
+        ]
+    }
+
+    async fn setup(pool: &sqlx::PgPool) -> RsvpService {
+        let service = RsvpService::new(ReservationManager::new(pool.clone()));
+        let resource = Resource::new("ocean-view-room-713", "Ocean view", "room", 1);
+        service.create_resource(Request::new(CreateResourceRequest { resource: Some(resource) }))
+            .await
+            .unwrap();
+        service
+    }
+}